@@ -18,7 +18,15 @@ use modules::{
 #[tokio::main]
 async fn main() -> Result<()> {
 	let args = Cli::parse();
+	Config::set_path(args.config.clone());
+
+	// Commands read the config file themselves, without falling back to default values or migrating it.
+	if let Some(command) = &args.command {
+		return command.run();
+	}
+
 	let config = Config::get();
+
 	let params = Params::merge(&config, &args).await?;
 
 	run(&params).await?.render(&params)?;
@@ -27,11 +35,17 @@ async fn main() -> Result<()> {
 	Ok(())
 }
 
-async fn run(params: &Params) -> Result<Product> {
-	let loc = Location::get(&params.config.address, &params.config.language).await?;
-	let weather = Weather::get(loc.lat, loc.lon, &params.config.units).await?;
-	let historical_weather =
-		Weather::get_dates(&params.historical_weather, loc.lat, loc.lon, &params.config.units).await?;
+async fn run(params: &Params) -> Result<Product<'_>> {
+	let loc = Location::get(&params.config.address, &params.config.language, &params.texts).await?;
+	let weather = Weather::get(loc.lat, loc.lon, params.forecast_range(), &params.config.units, &params.texts).await?;
+	let historical_weather = Weather::get_dates(
+		&params.historical_weather,
+		loc.lat,
+		loc.lon,
+		&params.config.units,
+		&params.texts,
+	)
+	.await?;
 
 	Ok(Product {
 		address: loc.name,
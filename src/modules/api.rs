@@ -64,10 +64,6 @@ impl Default for ApiQuery<'_> {
 	}
 }
 
-pub trait ErrorMessage {
-	fn error_message() -> String;
-}
-
 pub struct GeoIpLocationQuery;
 
 impl Api for GeoIpLocationQuery {
@@ -1,18 +1,36 @@
 use anyhow::{bail, Result};
 use chrono::NaiveDate;
-use clap::{Parser, ValueEnum};
+use clap::{
+	builder::{EnumValueParser, PossibleValue, TypedValueParser},
+	error::ErrorKind,
+	Arg, Parser, Subcommand, ValueEnum,
+};
+use clap_complete::Shell;
 use serde::{Deserialize, Serialize};
+use std::{ffi::OsStr, path::PathBuf};
 use strum_macros::AsRefStr;
 
+use super::forecast::{MAX_FORECAST_DAYS, MAX_PAST_DAYS};
+
 #[derive(Parser)]
-#[command(author, version, about, long_about = None, next_line_help = true)]
+#[command(author, version, about, long_about = None, next_line_help = true, disable_help_subcommand = true)]
 pub struct Cli {
-	/// Address to check the weather
+	#[command(subcommand)]
+	pub command: Option<Command>,
+
+	/// Address to check the weather, addresses named like a command follow `--` [e.g.: wthrr -- man]
 	pub address: Option<String>,
 
-	/// [e.g.: -f w,d]
-	#[arg(long, short, use_value_delimiter = true, value_name = "FORECAST,...")]
-	pub forecast: Vec<Forecast>,
+	/// Days can also be relative to today or dates [e.g.: -f w,d or -f +10,2026-10-25]
+	#[arg(
+		long,
+		short,
+		use_value_delimiter = true,
+		allow_negative_numbers = true,
+		value_name = "FORECAST,...",
+		value_parser = ForecastParser
+	)]
+	pub forecast: Vec<ForecastArg>,
 
 	/// [e.g.: -F 2021-12-31]
 	#[arg(long, short = 'F', use_value_delimiter = true, value_name = "%Y-%m-%d,...")]
@@ -27,6 +45,34 @@ pub struct Cli {
 	#[arg(short, long, value_parser = parse_language_code)]
 	pub language: Option<String>,
 
+	/// Render to another format instead of the terminal [e.g.: -o csv > weather.csv]
+	#[arg(long, short)]
+	pub output: Option<Output>,
+
+	/// Show the next hours from now instead of the whole day in today's hourly forecast [e.g.: -n 12]
+	#[arg(long, short, value_name = "HOURS", value_parser = clap::value_parser!(u8).range(1..))]
+	pub next: Option<u8>,
+
+	/// Hours between two labels of the hourly forecast [e.g.: --label-step 2]
+	#[arg(long, value_name = "HOURS", value_parser = clap::value_parser!(u8).range(1..=24))]
+	pub label_step: Option<u8>,
+
+	/// Fit the output into a terminal of this many columns instead of the detected width [e.g.: --width 80]
+	#[arg(long, short, value_name = "COLUMNS")]
+	pub width: Option<usize>,
+
+	/// Keep the Nerd Font icons when rendering to another format (emoji and unicode icons are always kept)
+	#[arg(long, requires = "output")]
+	pub icons: bool,
+
+	/// Layer a profile of the config file over its base values
+	#[arg(long, short, env = "WTHRR_PROFILE")]
+	pub profile: Option<String>,
+
+	/// Use another config file
+	#[arg(long, global = true, env = "WTHRR_CONFIG", value_name = "PATH")]
+	pub config: Option<PathBuf>,
+
 	/// Save the supplied values as default
 	#[arg(short, long, group = "config_file_action")]
 	pub save: bool,
@@ -36,6 +82,43 @@ pub struct Cli {
 	pub reset: bool,
 }
 
+#[derive(Subcommand)]
+pub enum Command {
+	/// Manage the config file
+	#[command(subcommand)]
+	Config(ConfigCommand),
+	/// Inspect the texts of a language
+	#[command(subcommand)]
+	Locales(LocalesCommand),
+	/// Print a shell completion script
+	#[command(hide = true)]
+	Completions { shell: Shell },
+	/// Print the man page
+	#[command(hide = true)]
+	Man,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+	/// Print a value [e.g.: config get gui.graph.style]
+	Get { key: String },
+	/// Save a value [e.g.: config set units.speed knots]
+	Set { key: String, value: String },
+	/// Open the config file in $EDITOR
+	Edit,
+	/// Check the config file for errors
+	Validate,
+	/// Print the location of the config file
+	Path,
+}
+
+#[derive(Subcommand)]
+pub enum LocalesCommand {
+	/// Report missing and unknown keys of a language [e.g.: locales check de_DE]
+	#[allow(clippy::doc_markdown)]
+	Check { lang: String },
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, AsRefStr, Serialize, Deserialize, Hash)]
 #[allow(non_camel_case_types)]
 pub enum Forecast {
@@ -62,6 +145,67 @@ pub enum Forecast {
 	su,
 }
 
+// A value of `--forecast`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForecastArg {
+	Named(Forecast),
+	Day(Day),
+}
+
+// A day of the forecast that is addressed by its distance from today or its date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Day {
+	Relative(i64),
+	Date(NaiveDate),
+}
+
+// Parses the named forecasts, days relative to today (`+10`, `-2`) and dates (`2026-10-25`).
+#[derive(Clone)]
+struct ForecastParser;
+
+impl TypedValueParser for ForecastParser {
+	type Value = ForecastArg;
+
+	fn parse_ref(&self, cmd: &clap::Command, arg: Option<&Arg>, value: &OsStr) -> Result<Self::Value, clap::Error> {
+		let text = value.to_string_lossy();
+		if text.starts_with(['+', '-']) {
+			let days = text.parse::<i64>().map_err(|_| {
+				cmd.clone()
+					.error(ErrorKind::InvalidValue, format!("invalid number of days '{text}'"))
+			})?;
+			if days < -i64::from(MAX_PAST_DAYS) || days >= i64::from(MAX_FORECAST_DAYS) {
+				return Err(cmd.clone().error(
+					ErrorKind::ValueValidation,
+					format!(
+						"'{text}' is out of range, days relative to today reach from -{MAX_PAST_DAYS} to +{}",
+						MAX_FORECAST_DAYS - 1
+					),
+				));
+			}
+			return Ok(ForecastArg::Day(Day::Relative(days)));
+		}
+		if let Ok(date) = NaiveDate::parse_from_str(&text, "%Y-%m-%d") {
+			return Ok(ForecastArg::Day(Day::Date(date)));
+		}
+		EnumValueParser::<Forecast>::new()
+			.parse_ref(cmd, arg, value)
+			.map(ForecastArg::Named)
+	}
+
+	fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+		Some(Box::new(
+			Forecast::value_variants()
+				.iter()
+				.filter_map(ValueEnum::to_possible_value)
+				.chain([
+					PossibleValue::new("+DAYS"),
+					PossibleValue::new("-DAYS"),
+					PossibleValue::new("%Y-%m-%d"),
+				]),
+		))
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, AsRefStr, Serialize, Deserialize)]
 #[strum(serialize_all = "snake_case")]
 pub enum UnitArg {
@@ -70,14 +214,16 @@ pub enum UnitArg {
 	Celsius,
 	#[value(name = "(f)ahrenheit", aliases = ["f", "fahrenheit"])]
 	Fahrenheit,
+	#[value(name = "(k)elvin", aliases = ["k", "kelvin"])]
+	Kelvin,
 	// Windspeed
 	Kmh,
 	Mph,
 	#[value(name = "(kn)ots", aliases = ["kn", "knots"])]
-	// Serialize as kn for open-meteo api call
-	#[strum(serialize = "kn")]
 	Knots,
 	Ms,
+	#[value(name = "bft", alias = "beaufort")]
+	Beaufort,
 	#[value(name = "12h", alias = "am_pm")]
 	AmPm,
 	#[value(name = "24h", alias = "military")]
@@ -87,6 +233,25 @@ pub enum UnitArg {
 	Mm,
 	#[value(name = "(in)ch", alias = "in")]
 	Inch,
+	// Pressure
+	Hpa,
+	Inhg,
+	Mmhg,
+	Kpa,
+	// Visibility
+	Km,
+	#[value(name = "(mi)les", aliases = ["mi", "miles"])]
+	Mi,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[allow(non_camel_case_types)]
+pub enum Output {
+	html,
+	#[value(aliases = ["md"])]
+	markdown,
+	csv,
+	tsv,
 }
 
 fn parse_language_code(s: &str) -> Result<String> {
@@ -95,3 +260,35 @@ fn parse_language_code(s: &str) -> Result<String> {
 	}
 	Ok(s.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn forecast_days() {
+		let args = Cli::try_parse_from(["wthrr", "-f", "w,+10,-2,2026-10-25"]).unwrap();
+		assert_eq!(
+			args.forecast,
+			[
+				ForecastArg::Named(Forecast::week),
+				ForecastArg::Day(Day::Relative(10)),
+				ForecastArg::Day(Day::Relative(-2)),
+				ForecastArg::Day(Day::Date(NaiveDate::from_ymd_opt(2026, 10, 25).unwrap())),
+			]
+		);
+		assert!(Cli::try_parse_from(["wthrr", "-f", "+16"]).is_err());
+		assert!(Cli::try_parse_from(["wthrr", "-f", "someday"]).is_err());
+	}
+
+	#[test]
+	fn addresses_named_like_commands() {
+		let args = Cli::try_parse_from(["wthrr", "man"]).unwrap();
+		assert!(matches!(args.command, Some(Command::Man)));
+		assert!(args.address.is_none());
+
+		let args = Cli::try_parse_from(["wthrr", "-f", "w", "--", "man"]).unwrap();
+		assert!(args.command.is_none());
+		assert_eq!(args.address.as_deref(), Some("man"));
+	}
+}
@@ -0,0 +1,188 @@
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{builder::PossibleValuesParser, CommandFactory, ValueEnum};
+use ron::error::SpannedError;
+use std::{
+	env,
+	io::{self, Write},
+	process,
+};
+
+use super::{
+	args::{Cli, Command, ConfigCommand, Forecast, LocalesCommand, UnitArg},
+	config::Config,
+	localization::Locales,
+};
+
+impl Command {
+	pub fn run(&self) -> Result<()> {
+		match self {
+			Self::Config(command) => command.run()?,
+			Self::Locales(command) => command.run()?,
+			Self::Completions { shell } => {
+				// A broken config file only costs the completion of its addresses and profiles.
+				let config = Config::read().unwrap_or_default();
+				let mut buffer = Vec::new();
+				clap_complete::generate(*shell, &mut completion_command(&config), "wthrr", &mut buffer);
+				print(&buffer)?;
+			}
+			Self::Man => {
+				let mut buffer = Vec::new();
+				clap_mangen::Man::new(Cli::command()).render(&mut buffer)?;
+				print(&buffer)?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+// Output that is piped into a command which exits early, e.g., `head`, is cut off without an error.
+fn print(output: &[u8]) -> Result<()> {
+	match io::stdout().write_all(output).and_then(|()| io::stdout().flush()) {
+		Err(error) if error.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+		result => Ok(result?),
+	}
+}
+
+impl ConfigCommand {
+	fn run(&self) -> Result<()> {
+		let path = Config::get_path();
+
+		match self {
+			Self::Get { key } => println!("{}", load()?.get_value(key)?),
+			Self::Set { key, value } => {
+				// The file gets written anyway, so an outdated one is migrated first.
+				let mut config = located(Config::load())?;
+				config.set_value(key, value)?;
+				config.store().context("Error saving config file.")?;
+			}
+			Self::Edit => {
+				if !path.is_file() {
+					Config::default().store().context("Error saving config file.")?;
+				}
+
+				let editor = env::var("VISUAL")
+					.or_else(|_| env::var("EDITOR"))
+					.unwrap_or_else(|_| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+				// The editor variable can contain arguments, e.g.: `code --wait`.
+				let mut editor_args = editor.split_whitespace();
+				let status = process::Command::new(editor_args.next().unwrap_or_default())
+					.args(editor_args)
+					.arg(&path)
+					.status()
+					.with_context(|| format!("Error opening the config file with `{editor}`."))?;
+				if !status.success() {
+					bail!("`{editor}` exited with {status}.");
+				}
+
+				located(Config::load())?;
+			}
+			Self::Validate => {
+				if path.is_file() {
+					load()?;
+					println!("{} is valid.", path.display());
+				} else {
+					println!("{} does not exist. Default values are used.", path.display());
+				}
+			}
+			Self::Path => println!("{}", path.display()),
+		}
+
+		Ok(())
+	}
+}
+
+impl LocalesCommand {
+	fn run(&self) -> Result<()> {
+		match self {
+			Self::Check { lang } => {
+				let (source, missing, extra) = Locales::check(lang)?;
+				for (title, keys) in [("Missing", &missing), ("Unknown", &extra)] {
+					if !keys.is_empty() {
+						println!("{title} keys in {source}:\n  {}", keys.join("\n  "));
+					}
+				}
+				if !missing.is_empty() || !extra.is_empty() {
+					bail!("{} missing and {} unknown keys.", missing.len(), extra.len());
+				}
+				println!("No missing or unknown keys in {source}.");
+			}
+		}
+
+		Ok(())
+	}
+}
+
+fn load() -> Result<Config> {
+	located(Config::read())
+}
+
+fn located(config: Result<Config, SpannedError>) -> Result<Config> {
+	config.map_err(|error| anyhow!("{}:{error}", Config::get_path().display()))
+}
+
+// Some value names are only meant for display, e.g., `(w)eek`. Completions offer the names and aliases
+// that are accepted as input instead. Addresses are completed with the addresses saved in the config and its profiles.
+fn completion_command(config: &Config) -> clap::Command {
+	let mut addresses = vec!["auto".to_string()];
+	for address in std::iter::once(&config.address).chain(config.profiles.values().filter_map(|p| p.address.as_ref())) {
+		if !address.is_empty() && address != "auto" && address != "arg_input" && !addresses.contains(address) {
+			addresses.push(address.clone());
+		}
+	}
+
+	Cli::command()
+		.mut_arg("address", |arg| arg.value_parser(PossibleValuesParser::new(addresses)))
+		.mut_arg("profile", |arg| {
+			arg.value_parser(PossibleValuesParser::new(config.profiles.keys()))
+		})
+		.mut_arg("forecast", |arg| arg.value_parser(input_values::<Forecast>()))
+		.mut_arg("units", |arg| arg.value_parser(input_values::<UnitArg>()))
+}
+
+fn input_values<T: ValueEnum>() -> PossibleValuesParser {
+	PossibleValuesParser::new(
+		T::value_variants()
+			.iter()
+			.filter_map(ValueEnum::to_possible_value)
+			.flat_map(|value| {
+				value
+					.get_name_and_aliases()
+					.filter(|name| !name.contains('('))
+					.map(str::to_string)
+					.collect::<Vec<_>>()
+			}),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::config::ConfigFile;
+	use clap_complete::Shell;
+
+	#[test]
+	fn completions_with_aliases_and_address() {
+		let mut config = Config {
+			address: "Berlin,DE".to_string(),
+			..Default::default()
+		};
+		config.profiles.insert(
+			"travel".to_string(),
+			ConfigFile {
+				address: Some("Lisbon,PT".to_string()),
+				..Default::default()
+			},
+		);
+
+		let mut script = Vec::new();
+		clap_complete::generate(Shell::Bash, &mut completion_command(&config), "wthrr", &mut script);
+		let script = String::from_utf8(script).unwrap();
+		let words = script.split(|c: char| c.is_whitespace() || c == '"').collect::<Vec<_>>();
+
+		for value in ["w", "d", "tom", "12h", "kn", "auto", "Berlin,DE", "Lisbon,PT", "travel"] {
+			assert!(words.contains(&value), "missing completion: {value}");
+		}
+		assert!(!words.contains(&"(w)eek"));
+	}
+}
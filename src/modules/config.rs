@@ -1,94 +1,855 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use dialoguer::console::style;
 use optional_struct::{optional_struct, Applicable};
-use ron::{
-	extensions::Extensions,
-	ser::{to_string_pretty, PrettyConfig},
-	Options,
-};
+use ron::{error::SpannedError, extensions::Extensions, Options};
 use serde::{Deserialize, Serialize};
 use std::{
-	collections::HashSet,
-	fs::{self, File},
-	io::Write,
+	collections::{BTreeMap, HashSet},
+	env, fs, iter,
+	ops::Range,
 	path::PathBuf,
+	sync::OnceLock,
 };
 
 use super::{
 	args::Forecast,
 	display::gui_config::{ConfigFileGui, Gui},
+	translation::Translation,
 	units::{ConfigFileUnits, Units},
 };
 
 #[optional_struct(ConfigFile)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
+	#[optional_serde_skip_none]
+	pub version: u32,
+	#[optional_serde_skip_none]
 	pub address: String,
+	#[optional_serde_skip_none]
 	pub language: String,
+	#[optional_serde_skip_none]
+	pub translation: Translation,
+	#[optional_serde_skip_none]
 	pub forecast: HashSet<Forecast>,
+	// Days of the week overview, extended when a later or earlier day is requested.
+	#[optional_serde_skip_none]
+	pub forecast_days: u8,
+	#[optional_serde_skip_none]
+	pub past_days: u8,
 	#[optional_rename(ConfigFileUnits)]
+	#[optional_wrap]
+	#[optional_serde_skip_none]
 	pub units: Units,
 	#[optional_rename(ConfigFileGui)]
+	#[optional_wrap]
+	#[optional_serde_skip_none]
 	pub gui: Gui,
+	// Partial configs that are layered over the values above when selected with `--profile`.
+	#[optional_skip_wrap]
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub profiles: BTreeMap<String, ConfigFile>,
 }
 
 impl Default for Config {
 	fn default() -> Self {
+		let language = "en_US";
 		Self {
+			version: CONFIG_VERSION,
 			address: String::new(),
 			forecast: HashSet::new(),
-			language: "en_US".to_string(),
-			units: Units::default(),
+			forecast_days: 7,
+			past_days: 0,
+			language: language.to_string(),
+			translation: Translation::default(),
+			units: Units::for_language(language),
 			gui: Gui::default(),
+			profiles: BTreeMap::new(),
 		}
 	}
 }
 
 pub const CONFIG_DIR_NAME: &str = "weathercrab";
 const CONFIG_FILE_NAME: &str = "wthrr.ron";
+const CONFIG_TEMPLATE: &str = include_str!("../../wthrr.ron");
+const CONFIG_VERSION: u32 = 1;
+const ENV_PREFIX: &str = "WTHRR_";
+
+// Upgrades a config file from the version at its index to the next one. Files without a version are at version 0.
+const MIGRATIONS: [fn(&str) -> Option<String>; CONFIG_VERSION as usize] = [
+	// The graph style used to be set directly at `gui.graph`.
+	|ron| {
+		let range = Document::new(ron)
+			.find("gui.graph")
+			.filter(|range| !ron[range.clone()].starts_with('('))?;
+		Some(format!(
+			"{}(style: {}){}",
+			&ron[..range.start],
+			&ron[range.clone()],
+			&ron[range.end..]
+		))
+	},
+];
+
+// Set once at startup by `--config` or `WTHRR_CONFIG`.
+static CUSTOM_PATH: OnceLock<PathBuf> = OnceLock::new();
 
 impl Config {
 	pub fn get() -> Self {
-		let mut config = Self::default();
+		Self::load().unwrap_or_else(|error| {
+			let warning = style(" Warning:").yellow();
+			eprintln!(
+				"{warning} {0}\n{1: >4}At: {error}.\n{1: >4}Falling back to default values.\n",
+				Self::get_path().display(),
+				"",
+			);
+			Self::default()
+		})
+	}
 
+	// Unlike `get`, parsing errors of an existing config file are returned.
+	pub fn load() -> Result<Self, SpannedError> {
 		let path = Self::get_path();
-		let Ok(file) = fs::read_to_string(&path) else { return config };
-
-		match Options::default()
-			.with_default_extension(Extensions::IMPLICIT_SOME)
-			.from_str::<ConfigFile>(&file)
-		{
-			Ok(contents) => contents.apply_to(&mut config),
-			Err(error) => {
-				let warning = style(" Warning:").yellow();
-				eprintln!(
-					"{warning} {0}\n{1: >4}At: {error}.\n{1: >4}Falling back to default values.\n",
+		let Ok(mut file) = fs::read_to_string(&path) else {
+			return Ok(Self::default());
+		};
+		if let Some((version, migrated)) = migrate(&file) {
+			let backup = PathBuf::from(format!("{}.v{version}.bak", path.display()));
+			match fs::copy(&path, &backup).and_then(|_| fs::write(&path, &migrated)) {
+				Ok(()) => eprintln!(
+					" Migrated {} to version {CONFIG_VERSION}. The previous file is kept at {}.\n",
 					path.display(),
-					"",
-				);
-				return config;
+					backup.display()
+				),
+				Err(error) => {
+					eprintln!("{} Error migrating {}: {error}.\n", style(" Warning:").yellow(), path.display());
+				}
 			}
+			file = migrated;
+		}
+
+		Ok(Self::from_file(Self::parse(&file)?))
+	}
+
+	// Like `load`, but an outdated file is only migrated in memory, so it can be inspected without being changed.
+	pub fn read() -> Result<Self, SpannedError> {
+		let Ok(file) = fs::read_to_string(Self::get_path()) else {
+			return Ok(Self::default());
 		};
+		let file = migrate(&file).map_or(file, |(_, migrated)| migrated);
+
+		Ok(Self::from_file(Self::parse(&file)?))
+	}
+
+	fn parse(s: &str) -> Result<ConfigFile, SpannedError> {
+		options().from_str::<ConfigFile>(s)
+	}
+
+	// Units that are left out follow the region of the language of the file.
+	fn from_file(file: ConfigFile) -> Self {
+		let mut config = Self::default();
+		if let Some(language) = &file.language {
+			config.units = Units::for_language(language);
+		}
+		file.apply_to(&mut config);
 
 		config
 	}
 
+	// Keys address nested values separated by dots, e.g.: `gui.graph.style`.
+	pub fn get_value(&self, key: &str) -> Result<String> {
+		let ron = options().to_string(self)?;
+		let range = Document::new(&ron)
+			.find(key)
+			.ok_or_else(|| anyhow!("Unknown config key `{key}`."))?;
+
+		Ok(ron[range].to_string())
+	}
+
+	pub fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
+		let ron = options().to_string(self)?;
+		let range = Document::new(&ron)
+			.find(key)
+			.ok_or_else(|| anyhow!("Unknown config key `{key}`."))?;
+		let replace = |value: &str| format!("{}{value}{}", &ron[..range.start], &ron[range.end..]);
+
+		*self = match options().from_str(&replace(value)) {
+			Ok(config) => config,
+			// Allow to omit the quotes of string values, e.g., `address Berlin,DE`.
+			Err(error) => options()
+				.from_str(&replace(&format!("{value:?}")))
+				.map_err(|_| anyhow!("Invalid value for `{key}`: {}.", error.code))?,
+		};
+
+		Ok(())
+	}
+
+	pub fn apply_profile(mut self, name: Option<&str>) -> Result<Self> {
+		let Some(name) = name else { return Ok(self) };
+		let profile = self
+			.profiles
+			.get(name)
+			.cloned()
+			.ok_or_else(|| anyhow!("Unknown profile `{name}`."))?;
+
+		// Profiles can't select other profiles.
+		let profiles = std::mem::take(&mut self.profiles);
+		profile.apply_to(&mut self);
+		self.profiles = profiles;
+
+		Ok(self)
+	}
+
+	// Every value can be overridden by an environment variable named after its key,
+	// e.g.: `WTHRR_UNITS_TEMPERATURE=fahrenheit` or `WTHRR_GUI_GRAPH_TIME_INDICATOR=false`.
+	pub fn merge_env(self) -> Result<Self> {
+		self.merge_env_with(|name| env::var(name).ok())
+	}
+
+	// Takes the lookup of the variables, so tests don't need to change the environment of the process.
+	fn merge_env_with(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+		for key in Document::new(&options().to_string(&self)?).keys() {
+			let name = Self::env_name(&key);
+			let Some(value) = var(&name) else { continue };
+			self.set_value(&key, &value).with_context(|| format!("Error reading {name}."))?;
+		}
+
+		Ok(self)
+	}
+
+	// Units that are set by neither the config file, the selected profile nor an environment variable follow the
+	// region of the language, which might have been changed by one of them or by the `--language` argument.
+	pub fn localize_units(self, profile: Option<&str>, language: &str) -> Self {
+		let file = fs::read_to_string(Self::get_path()).unwrap_or_default();
+		self.localize_units_with(&file, profile, language, |name| env::var(name).ok())
+	}
+
+	fn localize_units_with(
+		mut self,
+		file: &str,
+		profile: Option<&str>,
+		language: &str,
+		var: impl Fn(&str) -> Option<String>,
+	) -> Self {
+		let document = Document::new(file);
+		let profile = profile
+			.and_then(|name| self.profiles.get(name))
+			.and_then(|profile| profile.units)
+			.unwrap_or_default();
+		let is_unset = |field: &str, in_profile: bool| {
+			let key = format!("units.{field}");
+			!in_profile && document.find(&key).is_none() && var(&Self::env_name(&key)).is_none()
+		};
+
+		let defaults = Units::for_language(language);
+		if is_unset("temperature", profile.temperature.is_some()) {
+			self.units.temperature = defaults.temperature;
+		}
+		if is_unset("speed", profile.speed.is_some()) {
+			self.units.speed = defaults.speed;
+		}
+		if is_unset("time", profile.time.is_some()) {
+			self.units.time = defaults.time;
+		}
+		if is_unset("precipitation", profile.precipitation.is_some()) {
+			self.units.precipitation = defaults.precipitation;
+		}
+		if is_unset("pressure", profile.pressure.is_some()) {
+			self.units.pressure = defaults.pressure;
+		}
+		if is_unset("distance", profile.distance.is_some()) {
+			self.units.distance = defaults.distance;
+		}
+
+		self
+	}
+
+	pub fn env_name(key: &str) -> String {
+		format!("{ENV_PREFIX}{}", key.replace('.', "_").to_uppercase())
+	}
+
 	pub fn store(&self) -> Result<()> {
 		let path = Self::get_path();
 
 		let cfg_dir = path.parent().unwrap();
 		if !cfg_dir.is_dir() {
 			fs::create_dir_all(cfg_dir)?;
-		};
+		}
 
-		let mut file = File::create(path)?;
-		let output = to_string_pretty(self, PrettyConfig::default()).unwrap();
-		file.write_all(output.as_bytes())?;
+		// Values are edited in place, so comments and the order of the existing file or the template are kept.
+		let file = fs::read_to_string(&path)
+			.ok()
+			.filter(|file| Self::parse(file).is_ok())
+			.unwrap_or_else(|| CONFIG_TEMPLATE.to_string());
+		fs::write(path, self.edit(&file)?)?;
 
 		Ok(())
 	}
 
+	fn edit(&self, file: &str) -> Result<String> {
+		let stored = Self::from_file(Self::parse(file)?);
+
+		let mut file = file.to_string();
+		for key in Document::new(&options().to_string(self)?).keys() {
+			let value = self.get_value(&key)?;
+			if value != stored.get_value(&key)? {
+				file = Document::new(&file).set(&key, &value);
+			}
+		}
+
+		Ok(file)
+	}
+
+	pub fn set_path(path: Option<PathBuf>) {
+		if let Some(path) = path {
+			CUSTOM_PATH.get_or_init(|| path);
+		}
+	}
+
 	pub fn get_path() -> PathBuf {
+		CUSTOM_PATH.get().cloned().unwrap_or_else(Self::get_default_path)
+	}
+
+	pub fn get_default_path() -> PathBuf {
 		dirs::config_dir().unwrap().join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME)
 	}
 }
+
+fn options() -> Options {
+	Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+}
+
+// Returns the version of the file and its upgraded contents if any migration applies.
+fn migrate(file: &str) -> Option<(u32, String)> {
+	let document = Document::new(file);
+	let version = document
+		.find("version")
+		.and_then(|range| file[range].parse::<u32>().ok())
+		.unwrap_or_default();
+
+	let mut migrated = None;
+	for migration in MIGRATIONS.iter().skip(version as usize) {
+		if let Some(upgraded) = migration(migrated.as_deref().unwrap_or(file)) {
+			migrated = Some(upgraded);
+		}
+	}
+
+	migrated.map(|migrated| (version, Document::new(&migrated).set("version", &CONFIG_VERSION.to_string())))
+}
+
+// The number of `#` of a raw string following its `r`, unless it's a raw identifier like `r#type` or an ordinary one.
+fn raw_string_hashes(chars: impl Iterator<Item = char>) -> Option<usize> {
+	let mut hashes = 0;
+	for c in chars {
+		match c {
+			'#' => hashes += 1,
+			'"' => return Some(hashes),
+			_ => return None,
+		}
+	}
+	None
+}
+
+// A view of a ron struct that locates values by their keys. Comments and whitespace are skipped,
+// so the values of a config file can be replaced without touching the rest of it.
+struct Document<'a> {
+	ron: &'a str,
+	// `ron` with comments and the contents of string, raw string and char literals replaced by spaces.
+	code: String,
+}
+
+impl<'a> Document<'a> {
+	fn new(ron: &'a str) -> Self {
+		#[derive(Clone, Copy)]
+		enum State {
+			Code,
+			Literal(char),
+			Escaped(char),
+			// The number of `#` around a raw string, e.g., 1 for `r#"…"#`
+			RawLiteral(usize),
+			LineComment,
+			BlockComment,
+		}
+
+		let mut code = String::with_capacity(ron.len());
+		let mut state = State::Code;
+		let mut chars = ron.chars().peekable();
+		while let Some(c) = chars.next() {
+			let keep = match (state, c) {
+				(State::Code, 'r') => match raw_string_hashes(chars.clone()) {
+					Some(hashes) => {
+						// The opening `r#"` is kept as it is.
+						code.push(c);
+						code.extend(chars.by_ref().take(hashes + 1));
+						state = State::RawLiteral(hashes);
+						continue;
+					}
+					None => true,
+				},
+				(State::RawLiteral(hashes), '"')
+					if chars.clone().take(hashes).filter(|&c| c == '#').count() == hashes =>
+				{
+					code.push(c);
+					code.extend(chars.by_ref().take(hashes));
+					state = State::Code;
+					continue;
+				}
+				(State::Code, '"' | '\'') => {
+					state = State::Literal(c);
+					true
+				}
+				(State::Code, '/') if chars.peek() == Some(&'/') => {
+					state = State::LineComment;
+					false
+				}
+				(State::Code, '/') if chars.peek() == Some(&'*') => {
+					chars.next();
+					code.push(' ');
+					state = State::BlockComment;
+					false
+				}
+				(State::Literal(quote), _) if c == quote => {
+					state = State::Code;
+					true
+				}
+				(State::Literal(quote), '\\') => {
+					state = State::Escaped(quote);
+					false
+				}
+				(State::Escaped(quote), _) => {
+					state = State::Literal(quote);
+					false
+				}
+				(State::LineComment, '\n') => {
+					state = State::Code;
+					true
+				}
+				(State::BlockComment, '*') if chars.peek() == Some(&'/') => {
+					chars.next();
+					code.push(' ');
+					state = State::Code;
+					false
+				}
+				(State::Code, _) => true,
+				_ => false,
+			};
+
+			if keep {
+				code.push(c);
+			} else {
+				code.extend(iter::repeat(' ').take(c.len_utf8()));
+			}
+		}
+
+		Self { ron, code }
+	}
+
+	// The outermost struct, following optional attributes like `#![enable(implicit_some)]`.
+	fn root(&self) -> Option<Range<usize>> {
+		let mut start = 0;
+		while self.code[start..].trim_start().starts_with("#!") {
+			start += self.code[start..].find(']')? + 1;
+		}
+		start += self.code[start..].find(|c: char| !c.is_whitespace())?;
+		let end = self.code.trim_end().len();
+
+		(self.code[start..].starts_with('(') && self.code[..end].ends_with(')')).then_some(start..end)
+	}
+
+	// Splits a struct, e.g., `(a: 1, b: (c: 2))` into `[("a", 4..5), ("b", 10..16)]`.
+	fn fields(&self, range: Range<usize>) -> Vec<(&'a str, Range<usize>)> {
+		if !self.code[range.clone()].starts_with('(') {
+			return vec![];
+		}
+
+		let (mut fields, mut start, mut depth) = (vec![], range.start + 1, 0);
+		for (i, c) in self.code[range.clone()].char_indices().skip(1) {
+			let i = range.start + i;
+			match c {
+				'(' | '[' | '{' => depth += 1,
+				')' | ']' | '}' if depth > 0 => depth -= 1,
+				',' | ')' if depth == 0 => {
+					fields.push(start..i);
+					start = i + 1;
+				}
+				_ => {}
+			}
+		}
+
+		fields
+			.into_iter()
+			.filter_map(|field| {
+				let colon = field.start + self.code[field.clone()].find(':')?;
+				let name = self.trim(field.start..colon);
+				Some((&self.ron[name], self.trim(colon + 1..field.end)))
+			})
+			.collect()
+	}
+
+	fn find(&self, key: &str) -> Option<Range<usize>> {
+		key.split('.').try_fold(self.root()?, |range, field| {
+			self.fields(range)
+				.into_iter()
+				.find_map(|(name, value)| (name == field).then_some(value))
+		})
+	}
+
+	// Lists the keys of all non-struct values, e.g., `(a: 1, b: (c: 2))` results in `["a", "b.c"]`.
+	fn keys(&self) -> Vec<String> {
+		fn keys_in(document: &Document, range: Range<usize>, prefix: &str) -> Vec<String> {
+			document
+				.fields(range)
+				.into_iter()
+				.flat_map(|(name, value)| {
+					let key = format!("{prefix}{name}");
+					if document.code[value.clone()].starts_with('(') {
+						keys_in(document, value, &format!("{key}."))
+					} else {
+						vec![key]
+					}
+				})
+				.collect()
+		}
+
+		self.root().map(|root| keys_in(self, root, "")).unwrap_or_default()
+	}
+
+	// Replaces the value at `key`. Missing fields are appended to the innermost existing struct.
+	fn set(&self, key: &str, value: &str) -> String {
+		if let Some(range) = self.find(key) {
+			return format!("{}{value}{}", &self.ron[..range.start], &self.ron[range.end..]);
+		}
+
+		let fields = key.split('.').collect::<Vec<_>>();
+		let (parent, range) = (0..fields.len())
+			.rev()
+			.find_map(|i| {
+				let range = if i == 0 { self.root() } else { self.find(&fields[..i].join(".")) };
+				range
+					.filter(|range| self.code[range.clone()].starts_with('('))
+					.map(|range| (i, range))
+			})
+			.unwrap_or((0, 0..0));
+		let field = fields[parent + 1..]
+			.iter()
+			.rev()
+			.fold(value.to_string(), |acc, field| format!("({field}: {acc})"));
+		let field = format!("{}: {field}", fields[parent]);
+
+		if range.is_empty() {
+			return format!("({field})");
+		}
+
+		// Insert after the last field, following the indentation of the existing ones.
+		let last = self.code[..range.end - 1].trim_end().len();
+		let insertion = if last == range.start + 1 {
+			// Empty structs spanning several lines, e.g., with commented out fields, get the field on a line of its own.
+			let closing = self.ron[..range.end - 1].rfind('\n').map_or(0, |i| i + 1);
+			let mut lines = self.ron.get(range.start + 1..closing).unwrap_or_default().lines();
+			match lines.rfind(|line| !line.trim().is_empty()) {
+				Some(line) => {
+					let indent = &line[..line.len() - line.trim_start().len()];
+					(closing, format!("{indent}{field},\n"))
+				}
+				_ => (last, field),
+			}
+		} else {
+			let separator = if self.code[..last].ends_with(',') { "" } else { "," };
+			match self.fields(range.clone()).last() {
+				Some((_, previous)) if self.ron[range.clone()].contains('\n') => {
+					let line_start = self.ron[..previous.start].rfind('\n').map_or(0, |i| i + 1);
+					let line = &self.ron[line_start..previous.start];
+					let indent = &line[..line.len() - line.trim_start().len()];
+					let line_end = last + self.ron[last..range.end - 1].find('\n').unwrap_or(0);
+					return format!(
+						"{}{separator}{}\n{indent}{field},{}",
+						&self.ron[..last],
+						&self.ron[last..line_end],
+						&self.ron[line_end..]
+					);
+				}
+				_ => (last, format!("{separator} {field}")),
+			}
+		};
+
+		format!("{}{}{}", &self.ron[..insertion.0], insertion.1, &self.ron[insertion.0..])
+	}
+
+	fn trim(&self, range: Range<usize>) -> Range<usize> {
+		let s = &self.code[range.clone()];
+		let start = range.start + (s.len() - s.trim_start().len());
+		start..start.max(range.start + s.trim_end().len())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use super::*;
+	use crate::modules::{
+		display::{
+			graph::{GraphRows, GraphStyle, LineVariant},
+			gui_config::ColorVariant,
+		},
+		units::{Distance, Precipitation, Pressure, Speed, Temperature, Time},
+	};
+
+	#[test]
+	fn get_nested_value() -> Result<()> {
+		let mut config = Config::default();
+		config.gui.graph.style = GraphStyle::custom(['(', ',', '\'', '"', ')', '⠂', '⠁', '⠁']);
+
+		assert_eq!(config.get_value("language")?, "\"en_US\"");
+		assert_eq!(config.get_value("units.speed")?, "mph");
+		assert_eq!(
+			config.get_value("gui.graph.style")?,
+			"custom(('(',',','\\'','\"',')','⠂','⠁','⠁'))"
+		);
+		assert_eq!(config.get_value("gui.graph.time_indicator")?, "true");
+		assert!(config.get_value("gui.graph.colour").is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn set_nested_value() -> Result<()> {
+		let mut config = Config::default();
+
+		config.set_value("units.speed", "knots")?;
+		config.set_value("gui.graph.style", "lines(slim)")?;
+		config.set_value("address", "Berlin,DE")?;
+
+		assert_eq!(config.units.speed, Speed::knots);
+		assert_eq!(config.gui.graph.style, GraphStyle::lines(LineVariant::slim));
+		assert_eq!(config.address, "Berlin,DE");
+		assert_eq!(config.units.temperature, Config::default().units.temperature);
+		assert!(config.set_value("units.speed", "lightyears").is_err());
+		assert!(config.set_value("units.velocity", "knots").is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn apply_profile() -> Result<()> {
+		let config = Config::parse(
+			"(address: \"Berlin,DE\", units: (speed: mph), profiles: { \"ssh\": (gui: (color: plain, graph: (rowspan: single))) })",
+		)?;
+		let mut base = Config::default();
+		config.apply_to(&mut base);
+
+		let config = base.clone().apply_profile(Some("ssh"))?;
+
+		assert_eq!(config.gui.color, ColorVariant::plain);
+		assert_eq!(config.gui.graph.rowspan, GraphRows::single);
+		assert_eq!(config.gui.graph.style, base.gui.graph.style);
+		assert_eq!(config.units.speed, Speed::mph);
+		assert_eq!(config.address, "Berlin,DE");
+		assert_eq!(config.profiles.len(), 1);
+		assert!(base.apply_profile(Some("travel")).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn env_overrides() -> Result<()> {
+		let keys = Document::new(&options().to_string(&Config::default())?).keys();
+
+		assert!(keys.contains(&"gui.graph.time_indicator".to_string()));
+		assert!(!keys.contains(&"gui.graph".to_string()));
+		assert_eq!(Config::env_name("gui.graph.time_indicator"), "WTHRR_GUI_GRAPH_TIME_INDICATOR");
+
+		let vars = HashMap::from([("WTHRR_UNITS_SPEED", "knots"), ("WTHRR_GUI_GRAPH_TIME_INDICATOR", "false")]);
+		let config = Config::default().merge_env_with(|name| vars.get(name).map(ToString::to_string))?;
+
+		assert_eq!(config.units.speed, Speed::knots);
+		assert!(!config.gui.graph.time_indicator);
+		assert_eq!(config.language, Config::default().language);
+
+		Ok(())
+	}
+
+	#[test]
+	fn localize_units() -> Result<()> {
+		let file =
+			"(language: \"de_DE\", units: (temperature: fahrenheit), profiles: {\"us\": (units: (speed: knots))})";
+		let config = Config::from_file(Config::parse(file)?);
+
+		assert_eq!(config.units.temperature, Temperature::fahrenheit);
+		assert_eq!(config.units.time, Time::military);
+
+		let config = config
+			.apply_profile(Some("us"))?
+			.localize_units_with(file, Some("us"), "en_US", |_| None);
+
+		assert_eq!(config.units.temperature, Temperature::fahrenheit);
+		assert_eq!(config.units.speed, Speed::knots);
+		assert_eq!(config.units.time, Time::am_pm);
+		assert_eq!(config.units.precipitation, Precipitation::inch);
+		assert_eq!(config.units.pressure, Pressure::inhg);
+		assert_eq!(config.units.distance, Distance::mi);
+
+		// Units set by an environment variable don't follow the language.
+		let config = config.localize_units_with(file, None, "de_DE", |name| {
+			(name == "WTHRR_UNITS_TIME").then(|| "am_pm".to_string())
+		});
+		assert_eq!(config.units.time, Time::am_pm);
+		assert_eq!(config.units.precipitation, Precipitation::probability);
+
+		Ok(())
+	}
+
+	#[test]
+	fn template_has_default_values() -> Result<()> {
+		let mut config = Config::default();
+		Config::parse(CONFIG_TEMPLATE)?.apply_to(&mut config);
+
+		// Every value is listed in the template, including the otherwise omitted empty profiles.
+		// Units are commented out, so they follow the language.
+		let mut keys = Document::new(&options().to_string(&config)?).keys();
+		keys.retain(|key| !key.starts_with("units."));
+		keys.push("profiles".to_string());
+
+		assert_eq!(options().to_string(&config)?, options().to_string(&Config::default())?);
+		assert_eq!(Document::new(CONFIG_TEMPLATE).keys(), keys);
+
+		Ok(())
+	}
+
+	#[test]
+	fn edit_keeps_comments() -> Result<()> {
+		let mut config = Config::default();
+		config.set_value("units.speed", "knots")?;
+		config.set_value("units.time", "military")?;
+		config.set_value("gui.graph.style", "dotted")?;
+		config.set_value("address", "Berlin, DE")?;
+
+		let file = config.edit(CONFIG_TEMPLATE)?;
+		assert_eq!(file.lines().count(), CONFIG_TEMPLATE.lines().count() + 2);
+		assert!(file.contains("`mi`\n        speed: knots,\n        time: military,\n    ),"));
+		assert!(file.contains("            style: dotted,\n"));
+		assert!(file.contains("    address: \"Berlin, DE\", // Address to check the weather"));
+
+		// Values missing from a partial file are appended to the innermost existing struct.
+		let file = config.edit("(\n    // Units\n    units: (\n        time: am_pm, // 12h\n    ),\n)")?;
+		assert_eq!(
+			file,
+			"(\n    // Units\n    units: (\n        time: military, // 12h\n        speed: knots,\n    ),\n    \
+			address: \"Berlin, DE\",\n    gui: (graph: (style: dotted)),\n)"
+		);
+		let mut stored = Config::default();
+		Config::parse(&file)?.apply_to(&mut stored);
+		assert_eq!(stored.units.time, Time::military);
+		assert_eq!(stored.gui.graph.style, GraphStyle::dotted);
+
+		Ok(())
+	}
+
+	#[test]
+	fn document_inserts_missing_nested_keys() {
+		let ron = "(\n    gui: (\n        color: plain,\n    ),\n)";
+
+		assert_eq!(
+			Document::new(ron).set("gui.graph.style", "dotted"),
+			"(\n    gui: (\n        color: plain,\n        graph: (style: dotted),\n    ),\n)"
+		);
+		assert_eq!(
+			Document::new(ron).set("units.speed", "knots"),
+			"(\n    gui: (\n        color: plain,\n    ),\n    units: (speed: knots),\n)"
+		);
+		assert_eq!(
+			Document::new("(gui: ())").set("gui.greeting", "false"),
+			"(gui: (greeting: false))"
+		);
+		assert_eq!(
+			Document::new("(\n    gui: (),\n)").set("gui.greeting", "false"),
+			"(\n    gui: (greeting: false),\n)"
+		);
+		assert_eq!(Document::new("").set("gui.greeting", "false"), "(gui: (greeting: false))");
+	}
+
+	#[test]
+	fn document_skips_comments_and_strings() {
+		let ron = "// units: (speed: knots)\n(\n    /* gui: (color: plain), ) */\n    address: \"units: (speed: \\\"mph\\\")\", // units: (\n    units: (speed: kmh), // )\n)";
+		let document = Document::new(ron);
+
+		assert_eq!(document.keys(), ["address", "units.speed"]);
+		assert_eq!(document.find("gui.color"), None);
+		assert_eq!(&ron[document.find("units.speed").unwrap()], "kmh");
+		assert_eq!(&ron[document.find("address").unwrap()], "\"units: (speed: \\\"mph\\\")\"");
+		assert_eq!(
+			document.set("units.speed", "knots"),
+			ron.replace("units: (speed: kmh)", "units: (speed: knots)")
+		);
+	}
+
+	#[test]
+	fn document_skips_raw_strings() {
+		let ron = "(\n    address: r#\"a \"quote\" ), // not a comment\"#,\n    units: (speed: kmh),\n)";
+		let document = Document::new(ron);
+
+		assert_eq!(document.keys(), ["address", "units.speed"]);
+		assert_eq!(
+			&ron[document.find("address").unwrap()],
+			"r#\"a \"quote\" ), // not a comment\"#"
+		);
+		assert_eq!(&ron[document.find("units.speed").unwrap()], "kmh");
+		assert_eq!(Document::new("(address: r\"(\")").keys(), ["address"]);
+	}
+
+	#[test]
+	fn migrate_with_raw_strings() -> Result<()> {
+		let file =
+			"(\n    address: r#\"Berlin \"(Mitte)\", // gui: (graph: dotted)\"#,\n    gui: (graph: lines(slim)),\n)";
+
+		let (_, migrated) = migrate(file).unwrap();
+		let config = Config::parse(&migrated)?;
+
+		assert_eq!(config.address.as_deref(), Some("Berlin \"(Mitte)\", // gui: (graph: dotted)"));
+		assert_eq!(
+			config.gui.unwrap().graph.unwrap().style,
+			Some(GraphStyle::lines(LineVariant::slim))
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn document_values_with_parentheses() {
+		let ron = "(\n    address: \"Berlin (Mitte), DE\",\n    gui: (graph: (style: lines(slim), rows: double)),\n)";
+		let document = Document::new(ron);
+
+		assert_eq!(&ron[document.find("address").unwrap()], "\"Berlin (Mitte), DE\"");
+		assert_eq!(&ron[document.find("gui.graph.style").unwrap()], "lines(slim)");
+		assert_eq!(&ron[document.find("gui.graph.rows").unwrap()], "double");
+		assert_eq!(document.keys(), ["address", "gui.graph.style", "gui.graph.rows"]);
+
+		let edited = document.set("gui.graph.style", "custom((\"▁\", \")\"))");
+		let edited = Document::new(&edited).set("address", "\"Paris (11e)\"");
+		assert_eq!(
+			edited,
+			"(\n    address: \"Paris (11e)\",\n    gui: (graph: (style: custom((\"▁\", \")\")), rows: double)),\n)"
+		);
+		assert_eq!(&edited[Document::new(&edited).find("gui.graph.rows").unwrap()], "double");
+	}
+
+	#[test]
+	fn migrate_graph_style() -> Result<()> {
+		let file = "/* v0 */\n(\n    gui: (\n        graph: lines(slim), // style\n        greeting: false,\n    ),\n)";
+
+		let (version, migrated) = migrate(file).unwrap();
+
+		assert_eq!(version, 0);
+		assert_eq!(
+			migrated,
+			"/* v0 */\n(\n    gui: (\n        graph: (style: lines(slim)), // style\n        greeting: false,\n    ),\n    version: 1,\n)"
+		);
+		assert_eq!(
+			Config::parse(&migrated)?.gui.unwrap().graph.unwrap().style,
+			Some(GraphStyle::lines(LineVariant::slim))
+		);
+		assert!(migrate(&migrated).is_none());
+		assert!(migrate(CONFIG_TEMPLATE).is_none());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,241 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::ops::Range;
+
+use crate::modules::{
+	moon::{self, Moon},
+	params::Params,
+	sun::{self, SUNRISE},
+	weather::Weather,
+};
+
+use super::{
+	border::{Border, Separator},
+	gui_config::ConfigurableColor,
+	icons::Icon,
+	product::TOTAL_BORDER_PADDING,
+	utils::{align_cells, NO_TIME},
+};
+
+// Elevations of the sun during the golden hour, when the light is warm and soft, and the blue hour before and
+// after it, when the sky is deep blue.
+const GOLDEN_HOUR: Range<f64> = -4.0..6.0;
+const BLUE_HOUR: Range<f64> = -6.0..-4.0;
+
+// A day of the location, sampled once per minute.
+struct Day {
+	start: DateTime<Utc>,
+	minutes: i64,
+}
+
+impl Day {
+	fn new(weather: &Weather, date: NaiveDate) -> Option<Self> {
+		// Midnight can be skipped when the clocks are turned forward.
+		let midnight = |date: NaiveDate| {
+			["00:00", "01:00"]
+				.iter()
+				.find_map(|time| weather.local_datetime(&format!("{date}T{time}")))
+		};
+		let start = midnight(date)?;
+		let end = midnight(date.succ_opt()?)?;
+
+		Some(Self {
+			start: start.into(),
+			minutes: (end - start).num_minutes(),
+		})
+	}
+
+	fn time(&self, minute: f64) -> DateTime<Utc> {
+		self.start + Duration::milliseconds((minute * 60_000.0).round() as i64)
+	}
+
+	fn sample(&self, elevation: impl Fn(DateTime<Utc>) -> f64) -> Vec<f64> {
+		(0..=self.minutes)
+			.map(|minute| elevation(self.start + Duration::minutes(minute)))
+			.collect()
+	}
+}
+
+// Periods in minutes since the start of the day during which the sampled elevations lie in a band. Their bounds
+// are interpolated between the samples.
+#[allow(clippy::cast_precision_loss)]
+fn periods(samples: &[f64], band: &Range<f64>) -> Vec<Range<f64>> {
+	let Some(first) = samples.first() else {
+		return vec![];
+	};
+	let mut result = Vec::new();
+	let mut start = band.contains(first).then_some(0.0);
+
+	for (minute, pair) in samples.windows(2).enumerate() {
+		let (before, after) = (pair[0], pair[1]);
+		if band.contains(&before) == band.contains(&after) {
+			continue;
+		}
+		// The bound of the band that lies between the two samples
+		let bound = if (before.min(after)..before.max(after)).contains(&band.start) {
+			band.start
+		} else {
+			band.end
+		};
+		let crossing = minute as f64 + (bound - before) / (after - before);
+		match start.take() {
+			Some(start) => result.push(start..crossing),
+			None => start = Some(crossing),
+		}
+	}
+	if let Some(start) = start {
+		result.push(start..(samples.len() - 1) as f64);
+	}
+
+	result
+}
+
+fn day_length(samples: &[f64]) -> f64 {
+	periods(samples, &(SUNRISE..f64::INFINITY))
+		.iter()
+		.map(|period| period.end - period.start)
+		.sum()
+}
+
+// Splits a positive value into whole units and rounded sixtieths, e.g., 1.999 minutes into 2m 00s instead of 1m 59s.
+#[allow(clippy::cast_possible_truncation)]
+fn sexagesimal(value: f64) -> (i64, i64) {
+	let sixtieths = (value * 60.0).round() as i64;
+	(sixtieths / 60, sixtieths % 60)
+}
+
+// Rows of the moon phase, moonrise and moonset, day length, solar noon, golden hour and blue hour of a day.
+#[allow(clippy::cast_precision_loss)]
+pub fn prep(weather: &Weather, params: &Params, day_index: usize) -> Result<Vec<(String, String)>> {
+	let (t, icons) = (&params.texts.weather.astronomy, params.config.gui.icons);
+	let (lat, lon) = (weather.latitude, weather.longitude);
+	let date = NaiveDate::parse_from_str(&weather.daily.time[day_index], "%Y-%m-%d")?;
+	let day = Day::new(weather, date).context("Invalid time zone")?;
+	let yesterday = Day::new(weather, date.pred_opt().context("Invalid date")?).context("Invalid time zone")?;
+
+	let clock = |minute: f64| params.format_clock(weather, &weather.local_time(day.time(minute)));
+	let windows = |band: &Range<f64>, samples: &[f64]| -> Result<String> {
+		let windows = periods(samples, band)
+			.iter()
+			.map(|period| Ok(format!("{}–{}", clock(period.start)?, clock(period.end)?)))
+			.collect::<Result<Vec<_>>>()?;
+		Ok(if windows.is_empty() { NO_TIME.to_string() } else { windows.join("  ") })
+	};
+
+	// Moon
+	let moon = Moon::at(day.time(day.minutes as f64 / 2.0));
+	let moon_samples = day.sample(|time| moon::elevation(lat, lon, time));
+	let moon_periods = periods(&moon_samples, &(0.0..f64::INFINITY));
+	// The moon can rise or set twice a day, as its day is about 50 minutes longer than the solar one.
+	let crossings = |times: Vec<f64>| -> Result<String> {
+		let times = times.into_iter().map(clock).collect::<Result<Vec<_>>>()?;
+		Ok(if times.is_empty() { NO_TIME.to_string() } else { times.join(" ") })
+	};
+	let moonrise = crossings(
+		moon_periods
+			.iter()
+			.map(|period| period.start)
+			.filter(|start| *start > 0.0)
+			.collect(),
+	)?;
+	let moonset = crossings(
+		moon_periods
+			.iter()
+			.map(|period| period.end)
+			.filter(|end| *end < day.minutes as f64)
+			.collect(),
+	)?;
+
+	// Sun
+	let sun_samples = day.sample(|time| sun::position(lat, lon, time).0);
+	let length = day_length(&sun_samples);
+	let change = length - day_length(&yesterday.sample(|time| sun::position(lat, lon, time).0));
+	let (hours, minutes) = sexagesimal(length / 60.0);
+	let (change_minutes, change_seconds) = sexagesimal(change.abs());
+	let noon = sun_samples
+		.iter()
+		.enumerate()
+		.max_by(|a, b| a.1.total_cmp(b.1))
+		.map_or(0.0, |(minute, _)| minute as f64);
+
+	Ok(vec![
+		(
+			format!("{} {}", icons.moon(moon.phase), t.phase(moon.phase)),
+			format!(
+				"{}%  {} {moonrise}  {} {moonset}",
+				(moon.illumination * 100.0).round(),
+				icons.get(Icon::Moonrise),
+				icons.get(Icon::Moonset)
+			),
+		),
+		(
+			t.day_length.clone(),
+			format!(
+				"{hours}{h} {minutes:02}{m} ({}{change_minutes}{m} {change_seconds:02}{s})",
+				if change < 0.0 { '−' } else { '+' },
+				h = params.texts.units.hours,
+				m = params.texts.units.minutes,
+				s = params.texts.units.seconds,
+			),
+		),
+		(t.solar_noon.clone(), clock(noon)?),
+		(t.golden_hour.clone(), windows(&GOLDEN_HOUR, &sun_samples)?),
+		(t.blue_hour.clone(), windows(&BLUE_HOUR, &sun_samples)?),
+	])
+}
+
+// A blank line and the rows with their labels in cells of the given width.
+pub fn rows(cells: &[(String, String)], cell_width: usize, width: usize, params: &Params, rtl: bool) -> Vec<String> {
+	let gui = &params.config.gui;
+	let width_no_border_pad = width - TOTAL_BORDER_PADDING;
+
+	let mut result = vec![format!("{}", Separator::Blank.fmt(width, gui.border).border_color(gui))];
+	for (label, value) in cells {
+		result.push(format!(
+			"{} {} {}",
+			Border::L.fmt(gui.border).border_color(gui),
+			align_cells(&[(label, cell_width), (value, width_no_border_pad - cell_width)], rtl),
+			Border::R.fmt(gui.border).border_color(gui),
+		));
+	}
+
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::display::utils::common_tests::{init_test_params, init_test_product};
+
+	#[test]
+	fn periods_in_band() {
+		let samples = [-2.0, -1.0, 1.0, 3.0, 1.0, -1.0];
+		assert_eq!(periods(&samples, &(0.0..2.0)), vec![1.5..2.5, 3.5..4.5]);
+		assert_eq!(periods(&samples, &(0.0..f64::INFINITY)), vec![1.5..4.5]);
+		assert_eq!(periods(&samples, &(-5.0..5.0)), vec![0.0..5.0]);
+		assert!(periods(&samples, &(5.0..6.0)).is_empty());
+		assert!(periods(&[], &(0.0..2.0)).is_empty());
+		assert!((day_length(&[0.0; 1441]) - 1440.0).abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn rounded_durations() {
+		assert_eq!(sexagesimal(1.999), (2, 0));
+		assert_eq!(sexagesimal(1.5), (1, 30));
+		assert_eq!(sexagesimal(10.0 - 1.0 / 120.0), (10, 0));
+		assert_eq!(sexagesimal(0.0), (0, 0));
+	}
+
+	#[test]
+	fn polar_night() {
+		let mut weather = init_test_product().weather;
+		(weather.latitude, weather.longitude) = (69.65, 18.96);
+		weather.daily.time[0] = String::from("2024-12-21");
+
+		// The sun stays below the horizon in Tromsø, but its light is golden around noon.
+		let cells = prep(&weather, &init_test_params(), 0).unwrap();
+		assert_eq!(cells[1].1, "0h 00m (+0m 00s)");
+		assert_eq!(cells[3].1, "10:30–12:55");
+		assert_eq!(cells[4].1, "09:31–10:30  12:55–13:53");
+	}
+}
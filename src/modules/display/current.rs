@@ -1,14 +1,15 @@
 use anyhow::Result;
-use dialoguer::console::style;
 
-use crate::modules::{params::Params, units::Time};
+use crate::modules::params::Params;
 
 use super::{
+	astronomy,
 	border::{Border, BorderStyle, Edge, Separator},
 	gui_config::ConfigurableColor,
 	hourly,
+	icons::Icon,
 	product::{Product, MIN_CELL_WIDTH, MIN_WIDTH, TOTAL_BORDER_PADDING},
-	utils::pad_string_to_width,
+	utils::{align_cells, display_width, is_rtl},
 	weathercode::WeatherCode,
 	wind::WindDirection,
 };
@@ -21,77 +22,78 @@ pub struct Dimensions {
 #[allow(clippy::too_many_lines)]
 pub fn prep(product: &Product, params: &Params, add_hourly: bool) -> Result<(Vec<String>, Dimensions)> {
 	let weather = &product.weather;
-	let address = Product::trunc_address(product.address.clone(), 60);
+	let address = Product::trunc_address(product.address.clone(), params.layout.width().min(72) - 12);
 	let t = &params.texts.weather;
 
+	let (today, hour_index) = (weather.past_days, weather.current_hour_index());
+
 	// Times
-	let (current_hour, sunrise_hour, sunset_hour) = (
-		weather.current_weather.time[11..13].parse::<usize>().unwrap_or_default(),
-		weather.daily.sunrise[0][11..13].parse::<usize>().unwrap_or_default(),
-		weather.daily.sunset[0][11..13].parse::<usize>().unwrap_or_default(),
-	);
-	let sunrise = match params.config.units.time {
-		Time::am_pm => format!("{}:{}am", sunrise_hour, &weather.daily.sunrise[0][14..16]),
-		Time::military => weather.daily.sunrise[0][11..16].to_string(),
-	};
-	let sunset = match params.config.units.time {
-		Time::am_pm => format!("{}:{}pm", sunset_hour - 12, &weather.daily.sunset[0][14..16]),
-		Time::military => weather.daily.sunset[0][11..16].to_string(),
-	};
-	let night = current_hour < sunrise_hour || current_hour > sunset_hour;
+	let daylight = weather.daylight(&weather.current_weather.time);
+	let (sunrise, sunset) = params.format_sun_times(weather, today)?;
 
 	// Display Items
 	let temperature = format!(
-		"{:.1}{}",
-		weather.current_weather.temperature, weather.hourly_units.temperature_2m
+		"{}{}",
+		params.format_decimal(weather.current_weather.temperature),
+		params.temperature_unit()
 	);
 	let apparent_temperature = format!(
-		"{} {:.1}{}",
-		t.feels_like, weather.hourly.apparent_temperature[current_hour], weather.hourly_units.temperature_2m
+		"{} {}{}",
+		t.feels_like,
+		params.format_decimal(weather.hourly.apparent_temperature[hour_index]),
+		params.temperature_unit()
 	);
 	let humidity = format!(
 		"{}: {}{}",
-		t.humidity, weather.hourly.relativehumidity_2m[current_hour], weather.hourly_units.relativehumidity_2m,
+		t.humidity, weather.hourly.relativehumidity_2m[hour_index], weather.hourly_units.relativehumidity_2m,
 	);
 	let dewpoint = format!(
-		"{}: {:.1}{}",
-		t.dew_point, weather.hourly.dewpoint_2m[current_hour], weather.hourly_units.dewpoint_2m
+		"{}: {}{}",
+		t.dew_point,
+		params.format_decimal(weather.hourly.dewpoint_2m[hour_index]),
+		params.temperature_unit()
 	);
 	let wind_direction = WindDirection::get_direction(weather.current_weather.winddirection)?;
 	let wind = format!(
-		"{} {}{} {}",
-		wind_direction.get_icon(),
-		weather.current_weather.windspeed,
-		weather.hourly_units.windspeed_10m,
-		wind_direction
+		"{} {}",
+		params.config.gui.icons.wind(wind_direction),
+		params.format_wind(weather.current_weather.windspeed, wind_direction.localize(&t.wind_direction))
 	);
+	let icons = params.config.gui.icons;
 	let pressure = format!(
-		" {}{}",
-		weather.hourly.surface_pressure[current_hour], weather.hourly_units.surface_pressure
+		"{} {}",
+		icons.get(Icon::Pressure),
+		params.format_pressure(weather.hourly.surface_pressure[hour_index])
 	);
-	let sunrise = format!(" {sunrise}");
-	let sunset = format!(" {sunset}");
-	let wmo_code = WeatherCode::resolve(weather.current_weather.weathercode, night, &t.weather_code)?;
-
-	// Dimensions
-	// Overall width
-	let width = if add_hourly {
-		super::hourly::WIDTH
+	let visibility = format!(
+		"{}: {}",
+		t.visibility,
+		params.format_visibility(weather.hourly.visibility[hour_index])
+	);
+	let sunrise = format!("{} {sunrise}", icons.get(Icon::Sunrise));
+	let sunset = format!("{} {sunset}", icons.get(Icon::Sunset));
+	let wmo_code = WeatherCode::resolve(
+		weather.current_weather.weathercode,
+		daylight,
+		&t.weather_code,
+		&params.config.gui,
+	)?;
+	let astronomy = if params.config.gui.astronomy {
+		astronomy::prep(weather, params, today)?
 	} else {
-		let title_width = address.chars().count();
-		let title_padding = 2 * TOTAL_BORDER_PADDING; // 2 spaces on each side
-		if title_width > MIN_WIDTH {
-			title_width + title_padding
-		} else {
-			MIN_WIDTH + title_padding
-		}
+		Vec::new()
 	};
-	let width_no_border_pad = width - TOTAL_BORDER_PADDING;
+
+	// Dimensions
 	// Cell width
 	let cell_width = if add_hourly {
 		22
 	} else {
-		let longest_cell_width = humidity.chars().count();
+		// The wind cell is followed by the pressure without a separating space.
+		let longest_cell_width = astronomy
+			.iter()
+			.map(|(label, _)| display_width(label) + 1)
+			.fold(display_width(&humidity).max(display_width(&wind) + 1), usize::max);
 		// increase cell_width for languages with longer texts
 		if longest_cell_width > MIN_CELL_WIDTH {
 			longest_cell_width
@@ -99,24 +101,44 @@ pub fn prep(product: &Product, params: &Params, add_hourly: bool) -> Result<(Vec
 			MIN_CELL_WIDTH + TOTAL_BORDER_PADDING
 		}
 	};
+	// Overall width
+	let width = if add_hourly {
+		params.layout.width()
+	} else {
+		let title_width = display_width(&address);
+		let title_padding = 2 * TOTAL_BORDER_PADDING; // 2 spaces on each side
+		let row_width = astronomy
+			.iter()
+			.map(|(_, value)| display_width(value))
+			.fold(display_width(&pressure).max(display_width(&dewpoint)), usize::max)
+			+ cell_width
+			+ TOTAL_BORDER_PADDING;
+		if title_width > MIN_WIDTH {
+			title_width + title_padding
+		} else {
+			MIN_WIDTH + title_padding
+		}
+		.max(row_width)
+	};
+	let width_no_border_pad = width - TOTAL_BORDER_PADDING;
 
-	let gui = &params.config.gui;
+	let (gui, rtl) = (&params.config.gui, is_rtl(&params.config.language));
 
 	let mut result = Vec::<String>::new();
 
 	// Border Top
-	result.push(format!(
-		"{}",
-		&Edge::Top.fmt(width, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", &Edge::Top.fmt(width, gui.border).border_color(gui)));
 
 	// Address / Title
 	// TODO: restore centered title
 	result.push(format!(
 		"{} {} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		style(pad_string_to_width(&address, width_no_border_pad)).bold(),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		Border::L.fmt(gui.border).border_color(gui),
+		align_cells(&[(&address, width_no_border_pad)], rtl)
+			.as_str()
+			.heading_color(gui)
+			.bold(),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Separator
@@ -127,78 +149,94 @@ pub fn prep(product: &Product, params: &Params, add_hourly: bool) -> Result<(Vec
 			BorderStyle::solid => Separator::Solid.fmt(width, gui.border),
 			_ => Separator::Single.fmt(width, gui.border),
 		}
-		.plain_or_bright_black(gui.color),
+		.border_color(gui),
 	));
 
 	result.push(format!(
 		"{} {} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		style(pad_string_to_width(
-			&(wmo_code.icon.to_string() + " " + &wmo_code.interpretation + ", " + &temperature),
-			width_no_border_pad
-		))
-		.bold(),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		Border::L.fmt(gui.border).border_color(gui),
+		params.paint_temperatures(
+			&align_cells(
+				&[(
+					&(wmo_code.icon.clone() + " " + &wmo_code.interpretation + ", " + &temperature),
+					width_no_border_pad
+				)],
+				rtl
+			),
+			&[weather.current_weather.temperature],
+			true
+		),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Apparent Temperature
 	result.push(format!(
 		"{} {} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		pad_string_to_width(&apparent_temperature, width_no_border_pad),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		Border::L.fmt(gui.border).border_color(gui),
+		params.paint_temperatures(
+			&align_cells(&[(&apparent_temperature, width_no_border_pad)], rtl),
+			&[weather.hourly.apparent_temperature[hour_index]],
+			false
+		),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Blank Line
-	result.push(format!(
-		"{}",
-		Separator::Blank.fmt(width, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", Separator::Blank.fmt(width, gui.border).border_color(gui)));
 
 	// Humidity & Dewpoint
 	result.push(format!(
-		"{} {}{} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		pad_string_to_width(&humidity, cell_width),
+		"{} {} {}",
+		Border::L.fmt(gui.border).border_color(gui),
 		// NOTE: When using the Thai language, an apparent combining character issue was observed
 		// with the dew point, resulting in the border being displaced by one space or the border
 		// color being removed in some terminal/font configurations.
-		pad_string_to_width(&dewpoint, width_no_border_pad - cell_width),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		params.paint_temperatures(
+			&align_cells(&[(&humidity, cell_width), (&dewpoint, width_no_border_pad - cell_width)], rtl),
+			&[weather.hourly.dewpoint_2m[hour_index]],
+			false
+		),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Wind & Pressure
 	result.push(format!(
-		"{} {: <cell_width$}{: <width$} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		wind,
-		pressure,
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-		width = width_no_border_pad - cell_width
+		"{} {} {}",
+		Border::L.fmt(gui.border).border_color(gui),
+		align_cells(&[(&wind, cell_width), (&pressure, width_no_border_pad - cell_width)], rtl),
+		Border::R.fmt(gui.border).border_color(gui),
+	));
+
+	// Visibility
+	result.push(format!(
+		"{} {} {}",
+		Border::L.fmt(gui.border).border_color(gui),
+		align_cells(&[(&visibility, width_no_border_pad)], rtl),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Sunrise & Sunset
 	result.push(format!(
-		"{} {: <cell_width$}{: <width$} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		sunrise,
-		sunset,
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-		width = width_no_border_pad - cell_width
+		"{} {} {}",
+		Border::L.fmt(gui.border).border_color(gui),
+		align_cells(&[(&sunrise, cell_width), (&sunset, width_no_border_pad - cell_width)], rtl),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
+	// Astronomy
+	if !astronomy.is_empty() {
+		result.append(&mut astronomy::rows(&astronomy, cell_width, width, params, rtl));
+	}
+
 	// Hourly Forecast
 	if add_hourly {
-		for line in hourly::prep(product, params, 0)? {
+		for line in hourly::prep(product, params, today)? {
 			result.push(line);
 		}
-	};
+	}
 
 	// Border Bottom
-	result.push(format!(
-		"{}",
-		Edge::Bottom.fmt(width, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", Edge::Bottom.fmt(width, gui.border).border_color(gui)));
 
 	Ok((result, Dimensions { width, cell_width }))
 }
@@ -206,6 +244,8 @@ pub fn prep(product: &Product, params: &Params, add_hourly: bool) -> Result<(Vec
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::modules::display::graph::{GraphRows, Series, Stacking};
+	use crate::modules::display::hourly::Window;
 	use crate::modules::display::utils::common_tests::{
 		init_test_params, init_test_product, TEST_PARAMS, TEST_PRODUCT,
 	};
@@ -225,6 +265,7 @@ mod tests {
 │                                      │
 │ Humidity: 72%    Dew Point: 8.7°C    │
 │ ↑ 10.7km/h S      1001.3hPa         │
+│ Visibility: 24.1km                   │
 │  07:18           18:29             │
 ╰──────────────────────────────────────╯";
 
@@ -247,6 +288,7 @@ mod tests {
 │                                                                        │
 │ Humidity: 72%         Dew Point: 8.7°C                                 │
 │ ↑ 10.7km/h S           1001.3hPa                                      │
+│ Visibility: 24.1km                                                     │
 │  07:18                18:29                                          │
 │                                                                        │
 │ Hourly Forecast                                                        │
@@ -265,4 +307,140 @@ mod tests {
 		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
 		assert_eq!(want, have);
 	}
+
+	#[test]
+	fn output_with_next_hours() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.config.gui.hourly.window = Window::next(18);
+
+		let want = "\
+╭────────────────────────────────────────────────────────────────────────╮
+│ Berlin, Germany                                                        │
+├────────────────────────────────────────────────────────────────────────┤
+│  Overcast, 14.0°C                                                     │
+│ Feels like 11.8°C                                                      │
+│                                                                        │
+│ Humidity: 72%         Dew Point: 8.7°C                                 │
+│ ↑ 10.7km/h S           1001.3hPa                                      │
+│ Visibility: 24.1km                                                     │
+│  07:18                18:29                                          │
+│                                                                        │
+│ Next 18 Hours                                                          │
+│ 15.1/12.0°C ❲88󰖎❳                                                      │
+├┈┈┈╤┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ ₁₄     ₁₅     ₁₅     ₁₄     ₁₄     ₁₃     ₁₃     ₁₂     ₁₂   │
+│                                                                        │
+│🭻🭻🭻🭺🭷🭶🭶▔▔▔▔▔🭶🭶🭶🭸🭸🭸🭸🭸🭹🭹🭹🭹🭺🭺🭺🭺🭻🭻🭻▁                                        │
+│                                ▔▔▔▔🭶🭶🭶🭶🭷🭷🭷🭹🭹🭹🭹🭹🭺🭺🭺🭺🭻🭻🭻🭻▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁🭻│
+│  ₈      ₂₅       ₅       ₀       ₀       ₀       ₃      ₂₀      ₇₅   󰖎 │
+├┈┈┈╧┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│¹³˙⁰⁰   ¹⁵˙⁰⁰   ¹⁷˙⁰⁰   ¹⁹˙⁰⁰   ²¹˙⁰⁰   ²³˙⁰⁰   ⁰¹˙⁰⁰   ⁰³˙⁰⁰   ⁰⁵˙⁰⁰   │
+╰────────────────────────────────────────────────────────────────────────╯";
+
+		let (lines, _) = prep(test_product, &test_params, true).unwrap();
+		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
+		assert_eq!(want, have);
+	}
+
+	#[test]
+	fn output_with_several_series() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.config.gui.graph.series = vec![Series::temperature, Series::precipitation, Series::wind];
+		test_params.config.gui.graph.rowspan = GraphRows::single;
+
+		let want = "\
+╭────────────────────────────────────────────────────────────────────────╮
+│ Berlin, Germany                                                        │
+├────────────────────────────────────────────────────────────────────────┤
+│  Overcast, 14.0°C                                                     │
+│ Feels like 11.8°C                                                      │
+│                                                                        │
+│ Humidity: 72%         Dew Point: 8.7°C                                 │
+│ ↑ 10.7km/h S           1001.3hPa                                      │
+│ Visibility: 24.1km                                                     │
+│  07:18                18:29                                          │
+│                                                                        │
+│ Hourly Forecast                                                        │
+│ 15.1/6.8°C ❲25󰖎❳                                                       │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈╤┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│  ₉       ₈       ₇       ₇      ₁₂      ₁₅      ₁₄      ₁₄    │
+│                                                                        │
+│ Temperature 6.8–15.1°C                                                 │
+│🭻🭻🭻🭻🭻🭻▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁▁🭻🭺🭺🭹🭹🭹🭸🭸🭸🭷🭷🭷🭶🭶🭶🭶🭶🭶🭶🭶🭶🭶🭶🭶🭶🭶🭶🭶🭷🭷🭷🭷🭷🭷🭷🭷🭷🭷🭷🭷🭸🭸🭸│
+│ Precipitation 0.0–0.3mm                                                │
+│                                                   ██                   │
+│ Wind 4.0–15.5km/h                                                      │
+│▁▁▁▁▁▁▁▁▁▁▁🭻🭻🭻🭻🭻🭻🭻🭻🭻🭺🭺🭺🭺🭺🭺🭺🭺🭺🭹🭹🭹🭹🭹🭹🭹🭹🭹🭹🭹🭹🭸🭸🭸🭸🭸🭸🭸🭸🭸🭷🭷🭷🭷🭷🭷🭷🭷🭷🭶🭶🭶🭶🭶🭶🭶🭶🭶▔🭶🭶🭷│
+│  ₀        ₀        ₀        ₀        ₀       ₂₅        ₀        ₀    󰖎 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈╧┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│⁰⁰˙⁰⁰    ⁰³˙⁰⁰    ⁰⁶˙⁰⁰    ⁰⁹˙⁰⁰    ¹²˙⁰⁰    ¹⁵˙⁰⁰    ¹⁸˙⁰⁰    ²¹˙⁰⁰    │
+╰────────────────────────────────────────────────────────────────────────╯";
+
+		let (lines, _) = prep(test_product, &test_params, true).unwrap();
+		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
+		assert_eq!(want, have);
+
+		test_params.config.gui.graph.stacking = Stacking::overlay;
+		test_params.config.gui.graph.rowspan = GraphRows::double;
+		let want = "\
+╭────────────────────────────────────────────────────────────────────────╮
+│ Berlin, Germany                                                        │
+├────────────────────────────────────────────────────────────────────────┤
+│  Overcast, 14.0°C                                                     │
+│ Feels like 11.8°C                                                      │
+│                                                                        │
+│ Humidity: 72%         Dew Point: 8.7°C                                 │
+│ ↑ 10.7km/h S           1001.3hPa                                      │
+│ Visibility: 24.1km                                                     │
+│  07:18                18:29                                          │
+│                                                                        │
+│ Hourly Forecast                                                        │
+│ 15.1/6.8°C ❲25󰖎❳                                                       │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈╤┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│  ₉       ₈       ₇       ₇      ₁₂      ₁₅      ₁₄      ₁₄    │
+│                                                                        │
+│ Temperature 6.8–15.1°C  Precipitation 0.0–0.3mm  Wind 4.0–15.5km/h     │
+│                                    🭺🭹🭸🭷🭷🭶🭶🭶▔▔▔▔🭶🭶🭶🭶🭶🭶🭷🭷🭷🭷🭷🭷🭸🭸🭸🭸🭸🭸🭹🭹🭹🭹🭹🭹│
+│🭹🭹🭹🭺🭺🭺🭺🭺🭺🭻🭻🭻🭻🭻🭻▁▁▁▁▁▁▁▁▁▁▁▁🭻🭻🭺🭹🭸🭷🭶▔▔▔▔▔            ██                   │
+│  ₀        ₀        ₀        ₀        ₀       ₂₅        ₀        ₀    󰖎 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈╧┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│⁰⁰˙⁰⁰    ⁰³˙⁰⁰    ⁰⁶˙⁰⁰    ⁰⁹˙⁰⁰    ¹²˙⁰⁰    ¹⁵˙⁰⁰    ¹⁸˙⁰⁰    ²¹˙⁰⁰    │
+╰────────────────────────────────────────────────────────────────────────╯";
+
+		let (lines, _) = prep(test_product, &test_params, true).unwrap();
+		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
+		assert_eq!(want, have);
+	}
+
+	#[test]
+	fn output_with_astronomy() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.config.gui.astronomy = true;
+
+		let want = "\
+╭────────────────────────────────────────────╮
+│ Berlin, Germany                            │
+├────────────────────────────────────────────┤
+│  Overcast, 14.0°C                         │
+│ Feels like 11.8°C                          │
+│                                            │
+│ Humidity: 72%     Dew Point: 8.7°C         │
+│ ↑ 10.7km/h S       1001.3hPa              │
+│ Visibility: 24.1km                         │
+│  07:18            18:29                  │
+│                                            │
+│  Waxing Crescent 18%   12:49   19:43    │
+│ Day Length        11h 10m (−4m 02s)        │
+│ Solar Noon        12:54                    │
+│ Golden Hour       06:57–08:05  17:43–18:50 │
+│ Blue Hour         06:44–06:57  18:50–19:03 │
+╰────────────────────────────────────────────╯";
+
+		let (lines, _) = prep(test_product, &test_params, false).unwrap();
+		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
+		assert_eq!(want, have);
+	}
 }
@@ -1,138 +1,170 @@
 use anyhow::Result;
-use chrono::{Duration, NaiveDate};
-use dialoguer::console::style;
-use unicode_width::UnicodeWidthStr;
+use chrono::NaiveDate;
 
-use crate::modules::{localization::Locales, params::Params, units::Time};
+use crate::modules::{localization::Locales, params::Params};
 
 use super::{
+	astronomy,
 	border::{Border, BorderStyle, Edge, Separator},
 	gui_config::ConfigurableColor,
 	hourly,
-	hourly::WIDTH,
+	icons::Icon,
 	product::{Product, TOTAL_BORDER_PADDING},
-	utils::pad_string_to_width,
+	utils::{align_cells, display_width, is_rtl, mirror_cells, pad_cell},
 	weathercode::WeatherCode,
 };
 
+#[allow(clippy::too_many_lines)]
 pub fn prep(product: &Product, params: &Params, day_index: usize) -> Result<Vec<String>> {
 	let weather = &product.weather;
-	let address = Product::trunc_address(product.address.clone(), 60);
+	let width = params.layout.width();
+	let address = Product::trunc_address(product.address.clone(), width - 12);
 
 	// Times
-	let (current_hour, sunrise_hour, sunset_hour) = (
-		weather.current_weather.time[11..13].parse::<usize>().unwrap_or_default(),
-		weather.daily.sunrise[day_index][11..13].parse::<usize>().unwrap_or_default(),
-		weather.daily.sunset[day_index][11..13].parse::<usize>().unwrap_or_default(),
-	);
-	let sunrise = match params.config.units.time {
-		Time::am_pm => format!("{}:{}am", sunrise_hour, &weather.daily.sunrise[day_index][14..16]),
-		Time::military => weather.daily.sunrise[day_index][11..16].to_string(),
-	};
-	let sunset = match params.config.units.time {
-		Time::am_pm => format!("{}:{}pm", sunset_hour - 12, &weather.daily.sunset[day_index][14..16]),
-		Time::military => weather.daily.sunset[day_index][11..16].to_string(),
-	};
-	let night = current_hour < sunrise_hour || current_hour > sunset_hour;
+	let daylight = weather.daylight(&weather.current_weather.time);
+	let (sunrise, sunset) = params.format_sun_times(weather, day_index)?;
 
 	let temp_max_min = format!(
-		"{:.1}/{:.1}{}",
-		weather.daily.temperature_2m_max[day_index],
-		weather.daily.temperature_2m_min[day_index],
-		weather.daily_units.temperature_2m_max,
+		"{}/{}{}",
+		params.format_decimal(weather.daily.temperature_2m_max[day_index]),
+		params.format_decimal(weather.daily.temperature_2m_min[day_index]),
+		params.temperature_unit(),
 	);
 	let apparent_temp_max_min = format!(
-		"{} {:.1}/{:.1}{}",
+		"{} {}/{}{}",
 		params.texts.weather.feels_like,
-		weather.daily.apparent_temperature_max[day_index],
-		weather.daily.apparent_temperature_min[day_index],
-		weather.daily_units.temperature_2m_max,
+		params.format_decimal(weather.daily.apparent_temperature_max[day_index]),
+		params.format_decimal(weather.daily.apparent_temperature_min[day_index]),
+		params.temperature_unit(),
+	);
+	let icons = params.config.gui.icons;
+	let precipitation_probability_max = format!(
+		"{}{}{}{}",
+		icons.get(Icon::SummaryStart),
+		weather.daily.precipitation_probability_max[day_index],
+		icons.get(Icon::Probability),
+		icons.get(Icon::SummaryEnd)
 	);
-	let precipitation_probability_max = format!("❲{}󰖎❳", weather.daily.precipitation_probability_max[day_index]);
 
-	let dt = NaiveDate::parse_from_str(&product.weather.current_weather.time, "%Y-%m-%dT%H:%M")?
-		+ Duration::days(day_index.try_into()?);
+	let dt = NaiveDate::parse_from_str(&product.weather.daily.time[day_index], "%Y-%m-%d")?;
 	let lang = &params.config.language;
 	let date = format!(
-		" {}",
+		"{} {}",
+		icons.get(Icon::Calendar),
 		if lang == "en_US" || lang == "en" {
 			dt.format("%a, %e %b").to_string()
 		} else {
 			Locales::localize_date(dt, lang)?
 		}
 	);
-	let sunrise = format!(" {sunrise}");
-	let sunset = format!(" {sunset}");
-	let wmo_code =
-		WeatherCode::resolve(weather.daily.weathercode[day_index], night, &params.texts.weather.weather_code)?;
+	let sunrise = format!("{} {sunrise}", icons.get(Icon::Sunrise));
+	let sunset = format!("{} {sunset}", icons.get(Icon::Sunset));
+	let wmo_code = WeatherCode::resolve(
+		weather.daily.weathercode[day_index],
+		daylight,
+		&params.texts.weather.weather_code,
+		&params.config.gui,
+	)?;
 
-	let gui = &params.config.gui;
-	let width_no_border_pad = WIDTH - TOTAL_BORDER_PADDING;
+	let (gui, rtl) = (&params.config.gui, is_rtl(&params.config.language));
+	let width_no_border_pad = width - TOTAL_BORDER_PADDING;
 
 	let mut result = Vec::<String>::new();
 
 	// Border Top
-	result.push(format!(
-		"{}",
-		&Edge::Top.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", &Edge::Top.fmt(width, gui.border).border_color(gui)));
 
 	// Address / Title
 	result.push(format!(
 		"{} {} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		style(pad_string_to_width(&address, width_no_border_pad)).bold(),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		Border::L.fmt(gui.border).border_color(gui),
+		align_cells(&[(&address, width_no_border_pad)], rtl)
+			.as_str()
+			.heading_color(gui)
+			.bold(),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Separator
 	result.push(format!(
 		"{}",
 		&match &gui.border {
-			BorderStyle::double => Separator::Double.fmt(WIDTH, gui.border),
-			BorderStyle::solid => Separator::Solid.fmt(WIDTH, gui.border),
-			_ => Separator::Single.fmt(WIDTH, gui.border),
+			BorderStyle::double => Separator::Double.fmt(width, gui.border),
+			BorderStyle::solid => Separator::Solid.fmt(width, gui.border),
+			_ => Separator::Single.fmt(width, gui.border),
 		}
-		.plain_or_bright_black(gui.color),
+		.border_color(gui),
 	));
 
 	// Temperature & Weathercode
-	let temperature_and_weathercode = format!(
+	let mut temperature_and_weathercode = format!(
 		"{} {}, {} {}",
 		wmo_code.icon, wmo_code.interpretation, temp_max_min, precipitation_probability_max
 	);
+	// The icon stands in for the interpretation if the row doesn't fit into a condensed layout.
+	if display_width(&temperature_and_weathercode) + display_width(&date) > width_no_border_pad {
+		temperature_and_weathercode = format!("{} {} {}", wmo_code.icon, temp_max_min, precipitation_probability_max);
+	}
 	result.push(format!(
-		"{} {}{} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		style(pad_string_to_width(
-			&temperature_and_weathercode,
-			width_no_border_pad - date.width()
-		))
-		.bold(),
-		date,
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		"{} {} {}",
+		Border::L.fmt(gui.border).border_color(gui),
+		mirror_cells(
+			&[
+				params.paint_temperatures(
+					&pad_cell(&temperature_and_weathercode, width_no_border_pad - display_width(&date), rtl),
+					&[
+						weather.daily.temperature_2m_max[day_index],
+						weather.daily.temperature_2m_min[day_index]
+					],
+					true
+				),
+				date,
+			],
+			rtl
+		),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Apparent Temperature & Sun Rise & Sun Set
 	let sunrise_and_sunset = format!("{sunrise}  {sunset}");
 	result.push(format!(
-		"{} {}{} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		pad_string_to_width(&apparent_temp_max_min, width_no_border_pad - sunrise_and_sunset.width()),
-		sunrise_and_sunset,
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		"{} {} {}",
+		Border::L.fmt(gui.border).border_color(gui),
+		params.paint_temperatures(
+			&align_cells(
+				&[
+					(&apparent_temp_max_min, width_no_border_pad - display_width(&sunrise_and_sunset)),
+					(&sunrise_and_sunset, display_width(&sunrise_and_sunset)),
+				],
+				rtl
+			),
+			&[
+				weather.daily.apparent_temperature_max[day_index],
+				weather.daily.apparent_temperature_min[day_index]
+			],
+			false
+		),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
+	// Astronomy
+	if gui.astronomy {
+		let cells = astronomy::prep(weather, params, day_index)?;
+		let cell_width = cells
+			.iter()
+			.map(|(label, _)| display_width(label) + 2)
+			.max()
+			.unwrap_or_default();
+		result.append(&mut astronomy::rows(&cells, cell_width, width, params, rtl));
+	}
+
 	// Hourly Forecast
 	for line in hourly::prep(product, params, day_index)? {
 		result.push(line);
 	}
 
 	// Border Bottom
-	result.push(format!(
-		"{}",
-		Edge::Bottom.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", Edge::Bottom.fmt(width, gui.border).border_color(gui)));
 
 	Ok(result)
 }
@@ -202,4 +234,34 @@ mod tests {
 		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
 		assert_eq!(want, have);
 	}
+
+	#[test]
+	fn condensed_output() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.layout = hourly::Layout::fit(60);
+
+		let want = "\
+╭────────────────────────────────────────────────╮
+│ Berlin, Germany                                │
+├────────────────────────────────────────────────┤
+│  15.1/6.8°C ❲25󰖎❳                Mon,  7 Oct │
+│ Feels like 14.5/4.3°C          07:18   18:29 │
+│                                                │
+│ Hourly Forecast                                │
+│ 15.1/6.8°C ❲25󰖎❳                               │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈╤┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│  ₉          ₇         ₁₂         ₁₄       │
+│                                                │
+│                        🭺🭸🭷🭶🭶▔▔▔🭶🭶🭶🭶🭷🭷🭷🭷🭸🭸🭸🭸🭹🭹🭹🭹│
+│🭹🭹🭺🭺🭺🭺🭻🭻🭻🭻▁▁▁▁▁▁▁▁🭻🭺🭹🭷🭶▔                        │
+│  ₀           ₀           ₀           ₀       󰖎 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈╧┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│⁰⁰˙⁰⁰       ⁰⁶˙⁰⁰       ¹²˙⁰⁰       ¹⁸˙⁰⁰       │
+╰────────────────────────────────────────────────╯";
+
+		let lines = prep(test_product, &test_params, 0).unwrap();
+		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
+		assert_eq!(want, have);
+	}
 }
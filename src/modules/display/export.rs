@@ -0,0 +1,295 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::modules::{
+	args::{Forecast, Output},
+	forecast,
+	localization::UnitLocales,
+	params::Params,
+};
+
+use super::product::Product;
+
+// Column names follow the open-meteo fields and stay the same regardless of the configured units.
+const HOURLY_COLUMNS: [&str; 9] = [
+	"time",
+	"temperature_2m",
+	"relativehumidity_2m",
+	"apparent_temperature",
+	"surface_pressure",
+	"dewpoint_2m",
+	"precipitation",
+	"precipitation_probability",
+	"weathercode",
+];
+// The daily values are joined to the hours of their day, so the daily time column is left out.
+const DAILY_COLUMNS: [&str; 9] = [
+	"weathercode_daily",
+	"sunrise",
+	"sunset",
+	"temperature_2m_max",
+	"temperature_2m_min",
+	"apparent_temperature_max",
+	"apparent_temperature_min",
+	"precipitation_probability_max",
+	"precipitation_sum",
+];
+
+// Prints the hourly series of the requested days as a single table, each hour followed by the daily values of
+// its day. Rows are keyed by their timestamp, so overlapping historical and forecast data is only exported once.
+pub fn prep(product: &Product, params: &Params, output: Output) -> Result<Vec<String>> {
+	let delimiter = match output {
+		Output::tsv => '\t',
+		_ => ',',
+	};
+
+	let dates = requested_dates(product, params)?;
+	let daily = prep_daily(product);
+	let rows = prep_hourly(product).into_values().filter_map(|mut row| {
+		let date = row[0].split('T').next().unwrap_or_default().to_string();
+		if !dates.contains(&date) {
+			return None;
+		}
+		match daily.get(&date) {
+			Some(values) => row.extend(values.iter().cloned()),
+			None => row.resize(HOURLY_COLUMNS.len() + DAILY_COLUMNS.len(), String::new()),
+		}
+		Some(row)
+	});
+
+	let mut result = vec![line(&header(product, params), delimiter)];
+	result.extend(rows.map(|row| line(&row, delimiter)));
+	Ok(result)
+}
+
+// The days the terminal would render. The api returns the days around them as well, e.g., ten past days with a
+// recent historical date.
+fn requested_dates(product: &Product, params: &Params) -> Result<HashSet<String>> {
+	let (weather, forecast) = (&product.weather, &params.config.forecast);
+	let mut dates: HashSet<String> = params.historical_weather.iter().map(ToString::to_string).collect();
+
+	if forecast.is_empty() && params.days.is_empty() && params.historical_weather.is_empty() {
+		dates.extend(weather.daily.time.get(weather.past_days).cloned());
+	} else if !forecast.is_empty() || !params.days.is_empty() {
+		for i in forecast::get_indices(params, weather)? {
+			dates.insert(weather.daily.time[i].clone());
+		}
+		if forecast.contains(&Forecast::week) {
+			dates.extend(weather.daily.time.iter().cloned());
+		}
+	}
+
+	Ok(dates)
+}
+
+// Column names with the unit of their values, e.g. `temperature_2m (°C)`.
+fn header(product: &Product, params: &Params) -> Vec<String> {
+	let (hourly_units, daily_units) = (&product.weather.hourly_units, &product.weather.daily_units);
+	let pressure = UnitLocales::default().pressure(params.config.units.pressure).to_string();
+	let units = [
+		"iso8601",
+		&hourly_units.temperature_2m,
+		&hourly_units.relativehumidity_2m,
+		&hourly_units.apparent_temperature,
+		&pressure,
+		&hourly_units.dewpoint_2m,
+		&hourly_units.precipitation,
+		"%",
+		"wmo code",
+		"wmo code",
+		"iso8601",
+		"iso8601",
+		&daily_units.temperature_2m_max,
+		&daily_units.temperature_2m_min,
+		&daily_units.temperature_2m_max,
+		&daily_units.temperature_2m_min,
+		"%",
+		&hourly_units.precipitation,
+	];
+
+	HOURLY_COLUMNS
+		.iter()
+		.chain(DAILY_COLUMNS.iter())
+		.zip(units)
+		.map(|(name, unit)| format!("{name} ({unit})"))
+		.collect()
+}
+
+fn prep_hourly(product: &Product) -> BTreeMap<String, Vec<String>> {
+	let mut rows = BTreeMap::new();
+
+	for weather in product.historical_weather.values() {
+		let hourly = &weather.hourly;
+		let Some(time) = &hourly.time else { continue };
+		for (i, time) in time.iter().enumerate() {
+			rows.insert(
+				time.clone(),
+				vec![
+					time.clone(),
+					cell(hourly.temperature_2m.as_deref(), i),
+					cell(hourly.relativehumidity_2m.as_deref(), i),
+					cell(hourly.apparent_temperature.as_deref(), i),
+					cell(hourly.surface_pressure.as_deref(), i),
+					cell(hourly.dewpoint_2m.as_deref(), i),
+					cell(hourly.precipitation.as_deref(), i),
+					cell(hourly.precipitation_probability.as_deref(), i),
+					cell(hourly.weathercode.as_deref(), i),
+				],
+			);
+		}
+	}
+
+	let hourly = &product.weather.hourly;
+	for (i, time) in hourly.time.iter().enumerate() {
+		rows.insert(
+			time.clone(),
+			vec![
+				time.clone(),
+				cell(Some(&hourly.temperature_2m), i),
+				cell(Some(&hourly.relativehumidity_2m), i),
+				cell(Some(&hourly.apparent_temperature), i),
+				cell(Some(&hourly.surface_pressure), i),
+				cell(Some(&hourly.dewpoint_2m), i),
+				cell(Some(&hourly.precipitation), i),
+				cell(Some(&hourly.precipitation_probability), i),
+				cell(Some(&hourly.weathercode), i),
+			],
+		);
+	}
+
+	rows
+}
+
+// Daily values keyed by their date.
+fn prep_daily(product: &Product) -> BTreeMap<String, Vec<String>> {
+	let mut rows = BTreeMap::new();
+
+	for weather in product.historical_weather.values() {
+		let daily = &weather.daily;
+		let Some(time) = &daily.time else { continue };
+		for (i, time) in time.iter().enumerate() {
+			rows.insert(
+				time.clone(),
+				vec![
+					cell(daily.weathercode.as_deref(), i),
+					cell(daily.sunrise.as_deref(), i),
+					cell(daily.sunset.as_deref(), i),
+					cell(daily.temperature_2m_max.as_deref(), i),
+					cell(daily.temperature_2m_min.as_deref(), i),
+					cell(daily.apparent_temperature_max.as_deref(), i),
+					cell(daily.apparent_temperature_min.as_deref(), i),
+					cell(daily.precipitation_probability_max.as_deref(), i),
+					cell(daily.precipitation_sum.as_deref(), i),
+				],
+			);
+		}
+	}
+
+	let daily = &product.weather.daily;
+	for (i, time) in daily.time.iter().enumerate() {
+		rows.insert(
+			time.clone(),
+			vec![
+				cell(Some(&daily.weathercode), i),
+				cell(Some(&daily.sunrise), i),
+				cell(Some(&daily.sunset), i),
+				cell(Some(&daily.temperature_2m_max), i),
+				cell(Some(&daily.temperature_2m_min), i),
+				cell(Some(&daily.apparent_temperature_max), i),
+				cell(Some(&daily.apparent_temperature_min), i),
+				cell(Some(&daily.precipitation_probability_max), i),
+				cell(daily.precipitation_sum.as_deref(), i),
+			],
+		);
+	}
+
+	rows
+}
+
+fn cell<T: ToString>(values: Option<&[T]>, i: usize) -> String {
+	values.and_then(|v| v.get(i)).map(ToString::to_string).unwrap_or_default()
+}
+
+fn line(fields: &[String], delimiter: char) -> String {
+	fields
+		.iter()
+		.map(|field| quote(field, delimiter))
+		.collect::<Vec<_>>()
+		.join(&delimiter.to_string())
+}
+
+// Quotes a field that contains the delimiter, a quote or a line break, doubling its quotes (RFC 4180).
+fn quote(value: &str, delimiter: char) -> String {
+	if value.contains([delimiter, '"', '\n', '\r']) {
+		format!("\"{}\"", value.replace('"', "\"\""))
+	} else {
+		value.to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::args::Day;
+	use crate::modules::display::utils::common_tests::{
+		init_test_params, init_test_product, TEST_PARAMS, TEST_PRODUCT,
+	};
+
+	#[test]
+	fn csv_table() {
+		let (test_product, test_params) = (
+			TEST_PRODUCT.get_or_init(init_test_product),
+			TEST_PARAMS.get_or_init(init_test_params),
+		);
+
+		let table = prep(test_product, test_params, Output::csv).unwrap();
+
+		assert_eq!(table.len(), 24 + 1);
+		assert_eq!(
+			table[0],
+			"time (iso8601),temperature_2m (°C),relativehumidity_2m (%),apparent_temperature (°C),\
+			surface_pressure (hPa),dewpoint_2m (°C),precipitation (mm),precipitation_probability (%),\
+			weathercode (wmo code),weathercode_daily (wmo code),sunrise (iso8601),sunset (iso8601),\
+			temperature_2m_max (°C),temperature_2m_min (°C),apparent_temperature_max (°C),\
+			apparent_temperature_min (°C),precipitation_probability_max (%),precipitation_sum (mm)"
+		);
+		assert_eq!(
+			table[14],
+			"2024-10-07T13:00,13.7,72,11.8,1001.3,8.7,0,8,3,80,2024-10-07T07:18,2024-10-07T18:29,15.1,6.8,14.5,4.3,25,"
+		);
+	}
+
+	#[test]
+	fn tsv_table() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.config.forecast = HashSet::from([Forecast::week]);
+
+		let table = prep(test_product, &test_params, Output::tsv).unwrap();
+
+		assert_eq!(table.len(), 7 * 24 + 1);
+		assert!(table[0].starts_with("time (iso8601)\ttemperature_2m (°C)\t"));
+		assert_eq!(table[25].split('\t').nth(10), Some("2024-10-08T07:20"));
+	}
+
+	#[test]
+	fn requested_days_only() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.days = vec![Day::Relative(2)];
+
+		let table = prep(test_product, &test_params, Output::csv).unwrap();
+
+		assert_eq!(table.len(), 24 + 1);
+		assert!(table[1..].iter().all(|row| row.starts_with("2024-10-09T")));
+	}
+
+	#[test]
+	fn quoted_fields() {
+		assert_eq!(quote("13.7", ','), "13.7");
+		assert_eq!(quote("1,5", ','), "\"1,5\"");
+		assert_eq!(quote("1,5", '\t'), "1,5");
+		assert_eq!(quote("say \"hi\"", '\t'), "\"say \"\"hi\"\"\"");
+		assert_eq!(quote("a\tb", '\t'), "\"a\tb\"");
+	}
+}
@@ -2,12 +2,21 @@ use optional_struct::optional_struct;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
+use super::gui_config::{Gui, ThemeColor};
+
 #[optional_struct(ConfigFileGraphOpts)]
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct GraphOpts {
+	#[optional_serde_skip_none]
 	pub style: GraphStyle,
+	#[optional_serde_skip_none]
 	pub rowspan: GraphRows,
+	#[optional_serde_skip_none]
 	pub time_indicator: bool,
+	#[optional_serde_skip_none]
+	pub series: Vec<Series>,
+	#[optional_serde_skip_none]
+	pub stacking: Stacking,
 }
 
 impl Default for GraphOpts {
@@ -16,10 +25,53 @@ impl Default for GraphOpts {
 			style: GraphStyle::default(),
 			rowspan: GraphRows::default(),
 			time_indicator: true,
+			series: vec![Series::temperature],
+			stacking: Stacking::default(),
 		}
 	}
 }
 
+// Hourly values that can be plotted.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum Series {
+	temperature,
+	// Drawn as bars of the amount
+	precipitation,
+	wind,
+	humidity,
+	pressure,
+	uv_index,
+}
+
+impl Series {
+	pub fn color(self, gui: &Gui) -> ThemeColor {
+		let palette = gui.palette();
+		match self {
+			Self::temperature => palette.temperature,
+			Self::precipitation => palette.precipitation,
+			Self::wind => palette.wind,
+			Self::humidity => palette.humidity,
+			Self::pressure => palette.pressure,
+			Self::uv_index => palette.uv_index,
+		}
+	}
+
+	pub fn paint(self, text: &str, gui: &Gui) -> String {
+		self.color(gui).paint(text).to_string()
+	}
+}
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum Stacking {
+	// Each series gets rows of its own
+	#[default]
+	stacked,
+	// The series share their rows, earlier ones are drawn in front of later ones
+	overlay,
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
 #[allow(non_camel_case_types)]
 pub enum GraphStyle {
@@ -62,8 +114,96 @@ struct GraphLvls {
 }
 
 impl Graph {
+	// Rows from top to bottom.
+	fn rows(&self) -> Vec<&str> {
+		if self.1.is_empty() {
+			vec![&self.0]
+		} else {
+			vec![&self.1, &self.0]
+		}
+	}
+
+	// Rows of several graphs. Each column is drawn in the color given for its series and position.
+	pub fn combine(
+		graphs: &[(Series, Self)],
+		stacking: Stacking,
+		color: impl Fn(Series, usize) -> ThemeColor,
+	) -> Vec<String> {
+		if stacking == Stacking::stacked {
+			return graphs
+				.iter()
+				.flat_map(|(series, graph)| {
+					graph
+						.rows()
+						.into_iter()
+						.map(|row| paint_runs(row.chars().enumerate().map(|(col, glyph)| (color(*series, col), glyph))))
+						.collect::<Vec<_>>()
+				})
+				.collect();
+		}
+
+		let rows = graphs.iter().map(|(_, graph)| graph.rows().len()).max().unwrap_or_default();
+		(0..rows)
+			.map(|row| {
+				let cells = graphs
+					.iter()
+					.map(|(series, graph)| (*series, graph.rows().get(row).map(|r| r.chars().collect::<Vec<_>>())))
+					.collect::<Vec<_>>();
+				let width = cells
+					.iter()
+					.filter_map(|(_, c)| c.as_ref().map(Vec::len))
+					.max()
+					.unwrap_or_default();
+
+				paint_runs((0..width).map(|col| {
+					let (series, glyph) = cells
+						.iter()
+						.find_map(|(series, chars)| {
+							chars
+								.as_ref()
+								.and_then(|chars| chars.get(col))
+								.filter(|glyph| **glyph != ' ')
+								.map(|glyph| (*series, *glyph))
+						})
+						.unwrap_or((graphs[0].0, ' '));
+					(color(series, col), glyph)
+				}))
+			})
+			.collect()
+	}
+
+	// Bars of hourly amounts scaled to the largest one, in eighths of a row. Amounts above zero stay visible.
+	#[allow(
+		clippy::cast_precision_loss,
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss
+	)]
+	pub fn prepare_bars(values: &[f32], rowspan: GraphRows, cols_per_hour: usize) -> Self {
+		const BARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+		let mut graph = Self(String::new(), String::new());
+		// The last value belongs to the hour after the graph.
+		let values = &values[..values.len() - 1];
+		let max = values.iter().copied().fold(0.0, f32::max);
+		let rows = if rowspan == GraphRows::double { 2 } else { 1 };
+
+		for value in values {
+			let level = if max > 0.0 { (value / max * (8 * rows) as f32).ceil() as usize } else { 0 };
+			// The last column of each hour separates the bars.
+			for col in 0..cols_per_hour {
+				let gap = col == cols_per_hour - 1;
+				graph.0.push(if gap { ' ' } else { BARS[level.min(8)] });
+				if rows == 2 {
+					graph.1.push(if gap { ' ' } else { BARS[level.saturating_sub(8)] });
+				}
+			}
+		}
+
+		graph
+	}
+
 	#[allow(clippy::too_many_lines, clippy::cast_precision_loss)]
-	pub fn prepare_graph(temperatures: &[f32], graph_opts: &GraphOpts) -> Self {
+	pub fn prepare_graph(temperatures: &[f32], graph_opts: &GraphOpts, cols_per_hour: usize) -> Self {
 		let mut graph = Self(String::new(), String::new());
 		let style = graph_opts.style;
 		let rowspan = graph_opts.rowspan;
@@ -81,14 +221,15 @@ impl Graph {
 		graph_lvls.glyphs = GraphLvls::get_glyphs(&style, rowspan);
 		graph_lvls.margin = (max_temp - min_temp) / (graph_lvls.glyphs.len() - 1) as f32;
 
-		// Create Graph - calculate and push three characters per iteration to graph strings
+		// Create Graph - calculate and push `cols_per_hour` characters per iteration to graph strings.
+		// The first and last character connect to the neighbouring hours, the ones in between stay level.
 		// Single Line Graph
 		if rowspan == GraphRows::single {
 			for (i, temp) in temperatures.iter().enumerate() {
 				graph_lvls.current = ((temp - min_temp) / graph_lvls.margin) as usize;
 				graph_lvls.next = ((temperatures[i + 1] - min_temp) / graph_lvls.margin) as usize;
 
-				// char 1/3 - compare with last level
+				// first char - compare with last level
 				if let Some(last_lvl) = graph_lvls.last {
 					match Some(last_lvl.cmp(&graph_lvls.current)) {
 						Some(o) if o == Ordering::Less => graph.0.push(graph_lvls.glyphs[graph_lvls.get_idx_single(o)]),
@@ -105,10 +246,12 @@ impl Graph {
 					graph.0.push(graph_lvls.glyphs[graph_lvls.get_idx_single(Ordering::Equal)]);
 				}
 
-				// char 2/3
-				graph.0.push(graph_lvls.glyphs[graph_lvls.get_idx_single(Ordering::Equal)]);
+				// chars in between
+				for _ in 2..cols_per_hour {
+					graph.0.push(graph_lvls.glyphs[graph_lvls.get_idx_single(Ordering::Equal)]);
+				}
 
-				// char 3/3 - compare with next level
+				// last char - compare with next level
 				match Some(graph_lvls.next.cmp(&graph_lvls.current)) {
 					Some(o) if o == Ordering::Less => graph.0.push(graph_lvls.glyphs[graph_lvls.get_idx_single(o)]),
 					Some(o) if o == Ordering::Equal => graph.0.push(graph_lvls.glyphs[graph_lvls.get_idx_single(o)]),
@@ -116,7 +259,7 @@ impl Graph {
 					_ => {}
 				}
 
-				if i == 23 {
+				if i == temperatures.len() - 2 {
 					break;
 				}
 
@@ -133,7 +276,7 @@ impl Graph {
 
 			let graph_one_idx_sum = (graph_lvls.glyphs.len() - 1) / 2;
 
-			// Char 1/3 - compare with last level
+			// First char - compare with last level
 			if let Some(last_lvl) = graph_lvls.last {
 				if graph_lvls.current > graph_one_idx_sum {
 					match Some(last_lvl.cmp(&graph_lvls.current)) {
@@ -191,19 +334,21 @@ impl Graph {
 				}
 			}
 
-			// Char 2/3
-			if graph_lvls.current > graph_one_idx_sum {
-				match style {
-					GraphStyle::dotted => graph.0.push('⣿'),
-					_ => graph.0.push(' '),
+			// Chars in between
+			for _ in 2..cols_per_hour {
+				if graph_lvls.current > graph_one_idx_sum {
+					match style {
+						GraphStyle::dotted => graph.0.push('⣿'),
+						_ => graph.0.push(' '),
+					}
+					graph.1.push(graph_lvls.glyphs[graph_lvls.get_idx_double(Ordering::Equal)]);
+				} else {
+					graph.0.push(graph_lvls.glyphs[graph_lvls.get_idx_double(Ordering::Equal)]);
+					graph.1.push(' ');
 				}
-				graph.1.push(graph_lvls.glyphs[graph_lvls.get_idx_double(Ordering::Equal)]);
-			} else {
-				graph.0.push(graph_lvls.glyphs[graph_lvls.get_idx_double(Ordering::Equal)]);
-				graph.1.push(' ');
 			}
 
-			// Char 3/3 - compare with next level
+			// Last char - compare with next level
 			if graph_lvls.current > graph_one_idx_sum {
 				match Some(graph_lvls.next.cmp(&graph_lvls.current)) {
 					Some(o) if o == Ordering::Less => {
@@ -247,7 +392,7 @@ impl Graph {
 				}
 			}
 
-			if i == 23 {
+			if i == temperatures.len() - 2 {
 				break;
 			}
 
@@ -373,3 +518,51 @@ impl GraphLvls {
 		}
 	}
 }
+
+// Paints runs of glyphs that share a color at once.
+fn paint_runs(cells: impl Iterator<Item = (ThemeColor, char)>) -> String {
+	let mut result = String::new();
+	let mut run: Option<(ThemeColor, String)> = None;
+	for (color, glyph) in cells {
+		match &mut run {
+			Some((run_color, text)) if *run_color == color => text.push(glyph),
+			_ => {
+				if let Some((run_color, text)) = run.take() {
+					result.push_str(&run_color.paint(&text).to_string());
+				}
+				run = Some((color, glyph.to_string()));
+			}
+		}
+	}
+	if let Some((run_color, text)) = run {
+		result.push_str(&run_color.paint(&text).to_string());
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bars() {
+		let values = [0.0, 0.2, 1.0, 2.0, 0.0];
+		assert_eq!(Graph::prepare_bars(&values, GraphRows::single, 2).0, "  ▁ ▄ █ ");
+
+		let graph = Graph::prepare_bars(&values, GraphRows::double, 3);
+		assert_eq!(graph.rows(), ["         ██ ", "   ▂▂ ██ ██ "]);
+	}
+
+	#[test]
+	fn overlay_puts_earlier_series_in_front() {
+		let graphs = [
+			(Series::temperature, Graph("▔▔  ".to_string(), String::new())),
+			(Series::precipitation, Graph(" ▄▄▄".to_string(), String::new())),
+		];
+		assert_eq!(Graph::combine(&graphs, Stacking::overlay, |_, _| ThemeColor::none), ["▔▔▄▄"]);
+		assert_eq!(
+			Graph::combine(&graphs, Stacking::stacked, |_, _| ThemeColor::none),
+			["▔▔  ", " ▄▄▄"]
+		);
+	}
+}
@@ -1,28 +1,115 @@
-use dialoguer::console::{style, StyledObject};
+use dialoguer::console::{self, style, StyledObject};
 use optional_struct::{optional_struct, Applicable};
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, env};
 
 use crate::modules::display::{
 	border::BorderStyle,
 	graph::{ConfigFileGraphOpts, GraphOpts},
+	hourly::{ConfigFileHourlyOpts, HourlyOpts},
+	icons::IconSet,
+	week::WeekStyle,
 };
 
 #[optional_struct(ConfigFileGui)]
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Gui {
+	#[optional_serde_skip_none]
 	pub border: BorderStyle,
+	#[optional_serde_skip_none]
 	pub color: ColorVariant,
+	#[optional_rename(ConfigFileTheme)]
+	#[optional_wrap]
+	#[optional_serde_skip_none]
+	pub theme: Theme,
+	#[optional_serde_skip_none]
+	pub gradient: bool,
 	#[optional_rename(ConfigFileGraphOpts)]
+	#[optional_wrap]
+	#[optional_serde_skip_none]
 	pub graph: GraphOpts,
+	#[optional_rename(ConfigFileHourlyOpts)]
+	#[optional_wrap]
+	#[optional_serde_skip_none]
+	pub hourly: HourlyOpts,
+	#[optional_serde_skip_none]
+	pub week: WeekStyle,
+	#[optional_serde_skip_none]
+	pub own_time: bool,
+	#[optional_serde_skip_none]
+	pub astronomy: bool,
+	#[optional_serde_skip_none]
+	pub icons: IconSet,
+	// Icons of weather codes that replace the ones of the set
+	#[optional_serde_skip_none]
+	pub icon_overrides: BTreeMap<u8, String>,
+	#[optional_serde_skip_none]
 	pub greeting: bool,
 }
 
+// Presets of the theme colors.
 #[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
 #[allow(non_camel_case_types)]
 pub enum ColorVariant {
 	#[default]
 	default,
 	plain,
+	// Bright colors that stand out on dark backgrounds
+	high_contrast,
+	// Colors that stay apart with red-green color blindness, based on the Okabe-Ito palette
+	colorblind,
+}
+
+// A named terminal color, one of the 256 colors with `ansi(n)` or a truecolor `rgb(r, g, b)`. Truecolor falls back
+// to the closest of the 256 colors in terminals that don't announce support for it.
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum ThemeColor {
+	// The color of the preset chosen with `color`
+	#[default]
+	preset,
+	none,
+	black,
+	red,
+	green,
+	yellow,
+	blue,
+	magenta,
+	cyan,
+	white,
+	bright_black,
+	bright_red,
+	bright_green,
+	bright_yellow,
+	bright_blue,
+	bright_magenta,
+	bright_cyan,
+	bright_white,
+	ansi(u8),
+	rgb(u8, u8, u8),
+}
+
+#[optional_struct(ConfigFileTheme)]
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Theme {
+	#[optional_serde_skip_none]
+	pub border: ThemeColor,
+	#[optional_serde_skip_none]
+	pub temperature: ThemeColor,
+	#[optional_serde_skip_none]
+	pub precipitation: ThemeColor,
+	#[optional_serde_skip_none]
+	pub heading: ThemeColor,
+	// Colors of the other graph series
+	#[optional_serde_skip_none]
+	pub wind: ThemeColor,
+	#[optional_serde_skip_none]
+	pub humidity: ThemeColor,
+	#[optional_serde_skip_none]
+	pub pressure: ThemeColor,
+	#[optional_serde_skip_none]
+	pub uv_index: ThemeColor,
 }
 
 impl Default for Gui {
@@ -30,35 +117,304 @@ impl Default for Gui {
 		Self {
 			border: BorderStyle::default(),
 			color: ColorVariant::default,
+			theme: Theme::default(),
+			gradient: false,
 			graph: GraphOpts::default(),
+			hourly: HourlyOpts::default(),
+			week: WeekStyle::default(),
+			own_time: false,
+			astronomy: false,
+			icons: IconSet::default(),
+			icon_overrides: BTreeMap::new(),
 			greeting: true,
 		}
 	}
 }
 
-pub trait ConfigurableColor<'a> {
-	fn plain_or_bright_black(self, config_color: ColorVariant) -> StyledObject<&'a str>;
-	fn plain_or_yellow(self, config_color: ColorVariant) -> StyledObject<&'a str>;
-	fn plain_or_blue(self, config_color: ColorVariant) -> StyledObject<&'a str>;
-}
+impl Gui {
+	// The colors of the theme, falling back to the ones of the preset.
+	pub fn palette(&self) -> Theme {
+		let defaults = match self.color {
+			ColorVariant::default => Theme {
+				border: ThemeColor::bright_black,
+				temperature: ThemeColor::yellow,
+				precipitation: ThemeColor::blue,
+				heading: ThemeColor::none,
+				wind: ThemeColor::cyan,
+				humidity: ThemeColor::green,
+				pressure: ThemeColor::magenta,
+				uv_index: ThemeColor::red,
+			},
+			ColorVariant::plain => Theme {
+				border: ThemeColor::none,
+				temperature: ThemeColor::none,
+				precipitation: ThemeColor::none,
+				heading: ThemeColor::none,
+				wind: ThemeColor::none,
+				humidity: ThemeColor::none,
+				pressure: ThemeColor::none,
+				uv_index: ThemeColor::none,
+			},
+			ColorVariant::high_contrast => Theme {
+				border: ThemeColor::white,
+				temperature: ThemeColor::bright_yellow,
+				precipitation: ThemeColor::bright_cyan,
+				heading: ThemeColor::bright_white,
+				wind: ThemeColor::bright_green,
+				humidity: ThemeColor::bright_blue,
+				pressure: ThemeColor::bright_magenta,
+				uv_index: ThemeColor::bright_red,
+			},
+			ColorVariant::colorblind => Theme {
+				border: ThemeColor::bright_black,
+				temperature: ThemeColor::rgb(230, 159, 0),
+				precipitation: ThemeColor::rgb(86, 180, 233),
+				heading: ThemeColor::none,
+				wind: ThemeColor::rgb(0, 158, 115),
+				humidity: ThemeColor::rgb(0, 114, 178),
+				pressure: ThemeColor::rgb(204, 121, 167),
+				uv_index: ThemeColor::rgb(213, 94, 0),
+			},
+		};
+		let pick = |own: ThemeColor, default: ThemeColor| if own == ThemeColor::preset { default } else { own };
 
-impl<'a> ConfigurableColor<'a> for &'a str {
-	fn plain_or_bright_black(self, config_color: ColorVariant) -> StyledObject<&'a str> {
-		match config_color {
-			ColorVariant::plain => style(self),
-			ColorVariant::default => style(self).black().bright(),
+		Theme {
+			border: pick(self.theme.border, defaults.border),
+			temperature: pick(self.theme.temperature, defaults.temperature),
+			precipitation: pick(self.theme.precipitation, defaults.precipitation),
+			heading: pick(self.theme.heading, defaults.heading),
+			wind: pick(self.theme.wind, defaults.wind),
+			humidity: pick(self.theme.humidity, defaults.humidity),
+			pressure: pick(self.theme.pressure, defaults.pressure),
+			uv_index: pick(self.theme.uv_index, defaults.uv_index),
 		}
 	}
-	fn plain_or_yellow(self, config_color: ColorVariant) -> StyledObject<&'a str> {
-		match config_color {
-			ColorVariant::plain => style(self),
-			ColorVariant::default => style(self).yellow(),
+
+	// Color of a temperature: along the gradient if it's enabled, otherwise the one of the theme.
+	pub fn temperature_color(&self, celsius: f32) -> ThemeColor {
+		if self.gradient {
+			self.gradient(celsius)
+		} else {
+			self.palette().temperature
 		}
 	}
-	fn plain_or_blue(self, config_color: ColorVariant) -> StyledObject<&'a str> {
-		match config_color {
-			ColorVariant::plain => style(self),
-			ColorVariant::default => style(self).blue(),
+
+	// Color of a temperature along the cold-to-hot gradient of the preset.
+	#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+	pub fn gradient(&self, celsius: f32) -> ThemeColor {
+		let stops: &[(f32, (u8, u8, u8))] = match self.color {
+			ColorVariant::plain => return ThemeColor::none,
+			ColorVariant::default => &[
+				(-10.0, (80, 120, 255)),
+				(0.0, (0, 190, 255)),
+				(10.0, (0, 200, 120)),
+				(20.0, (230, 210, 0)),
+				(30.0, (255, 120, 0)),
+				(40.0, (220, 30, 30)),
+			],
+			ColorVariant::high_contrast => &[
+				(-10.0, (130, 170, 255)),
+				(0.0, (0, 255, 255)),
+				(10.0, (0, 255, 130)),
+				(20.0, (255, 255, 0)),
+				(30.0, (255, 160, 0)),
+				(40.0, (255, 70, 70)),
+			],
+			ColorVariant::colorblind => &[
+				(-10.0, (0, 114, 178)),
+				(5.0, (86, 180, 233)),
+				(20.0, (240, 228, 66)),
+				(30.0, (230, 159, 0)),
+				(40.0, (213, 94, 0)),
+			],
+		};
+
+		let upper = stops.iter().position(|(t, _)| *t > celsius).unwrap_or(stops.len() - 1).max(1);
+		let ((t0, c0), (t1, c1)) = (stops[upper - 1], stops[upper]);
+		let ratio = ((celsius - t0) / (t1 - t0)).clamp(0.0, 1.0);
+		let mix = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * ratio).round() as u8;
+		ThemeColor::rgb(mix(c0.0, c1.0), mix(c0.1, c1.1), mix(c0.2, c1.2))
+	}
+}
+
+impl ThemeColor {
+	pub fn paint(self, text: &str) -> StyledObject<String> {
+		let styled = style(text.to_string());
+		match self {
+			Self::preset | Self::none => styled,
+			Self::black => styled.black(),
+			Self::red => styled.red(),
+			Self::green => styled.green(),
+			Self::yellow => styled.yellow(),
+			Self::blue => styled.blue(),
+			Self::magenta => styled.magenta(),
+			Self::cyan => styled.cyan(),
+			Self::white => styled.white(),
+			Self::bright_black => styled.black().bright(),
+			Self::bright_red => styled.red().bright(),
+			Self::bright_green => styled.green().bright(),
+			Self::bright_yellow => styled.yellow().bright(),
+			Self::bright_blue => styled.blue().bright(),
+			Self::bright_magenta => styled.magenta().bright(),
+			Self::bright_cyan => styled.cyan().bright(),
+			Self::bright_white => styled.white().bright(),
+			Self::ansi(n) => styled.color256(n),
+			// The console crate only knows the 256 colors, so truecolor is set around the text.
+			Self::rgb(r, g, b) if supports_truecolor() => style(format!("\x1b[38;2;{r};{g};{b}m{text}\x1b[39m")),
+			Self::rgb(r, g, b) => styled.color256(to_ansi256(r, g, b)),
 		}
 	}
+
+	// Hex value for other formats. The named colors follow the Solarized accents.
+	pub fn hex(self) -> Option<String> {
+		let (r, g, b) = match self {
+			Self::preset | Self::none => return None,
+			Self::black => (0x07, 0x36, 0x42),
+			Self::red => (0xdc, 0x32, 0x2f),
+			Self::green => (0x85, 0x99, 0x00),
+			Self::yellow => (0xb5, 0x89, 0x00),
+			Self::blue => (0x26, 0x8b, 0xd2),
+			Self::magenta => (0xd3, 0x36, 0x82),
+			Self::cyan => (0x2a, 0xa1, 0x98),
+			Self::white => (0xee, 0xe8, 0xd5),
+			Self::bright_black => (0x80, 0x80, 0x80),
+			Self::bright_red => (0xcb, 0x4b, 0x16),
+			Self::bright_green => (0x93, 0xa1, 0x00),
+			Self::bright_yellow => (0xe0, 0xb0, 0x00),
+			Self::bright_blue => (0x83, 0x94, 0x96),
+			Self::bright_magenta => (0x6c, 0x71, 0xc4),
+			Self::bright_cyan => (0x93, 0xa1, 0xa1),
+			Self::bright_white => (0xfd, 0xf6, 0xe3),
+			Self::ansi(n) => from_ansi256(n),
+			Self::rgb(r, g, b) => (r, g, b),
+		};
+		Some(format!("#{r:02x}{g:02x}{b:02x}"))
+	}
+}
+
+fn supports_truecolor() -> bool {
+	console::colors_enabled() && env::var("COLORTERM").is_ok_and(|value| value == "truecolor" || value == "24bit")
+}
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// Closest color of the 6×6×6 cube of the 256 colors.
+#[allow(clippy::cast_possible_truncation)]
+fn to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+	let level = |c: u8| {
+		(0..CUBE_LEVELS.len())
+			.min_by_key(|&i| CUBE_LEVELS[i].abs_diff(c))
+			.unwrap_or_default() as u8
+	};
+	16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+fn from_ansi256(n: u8) -> (u8, u8, u8) {
+	const BASIC: [(u8, u8, u8); 16] = [
+		(0, 0, 0),
+		(128, 0, 0),
+		(0, 128, 0),
+		(128, 128, 0),
+		(0, 0, 128),
+		(128, 0, 128),
+		(0, 128, 128),
+		(192, 192, 192),
+		(128, 128, 128),
+		(255, 0, 0),
+		(0, 255, 0),
+		(255, 255, 0),
+		(0, 0, 255),
+		(255, 0, 255),
+		(0, 255, 255),
+		(255, 255, 255),
+	];
+	let level = |l: u8| CUBE_LEVELS[usize::from(l)];
+	match n {
+		0..=15 => BASIC[usize::from(n)],
+		16..=231 => (level((n - 16) / 36), level((n - 16) / 6 % 6), level((n - 16) % 6)),
+		_ => (8 + 10 * (n - 232), 8 + 10 * (n - 232), 8 + 10 * (n - 232)),
+	}
+}
+
+pub trait ConfigurableColor {
+	fn border_color(self, gui: &Gui) -> StyledObject<String>;
+	fn temperature_color(self, gui: &Gui) -> StyledObject<String>;
+	fn precipitation_color(self, gui: &Gui) -> StyledObject<String>;
+	fn heading_color(self, gui: &Gui) -> StyledObject<String>;
+}
+
+impl ConfigurableColor for &str {
+	fn border_color(self, gui: &Gui) -> StyledObject<String> {
+		gui.palette().border.paint(self)
+	}
+	fn temperature_color(self, gui: &Gui) -> StyledObject<String> {
+		gui.palette().temperature.paint(self)
+	}
+	fn precipitation_color(self, gui: &Gui) -> StyledObject<String> {
+		gui.palette().precipitation.paint(self)
+	}
+	fn heading_color(self, gui: &Gui) -> StyledObject<String> {
+		gui.palette().heading.paint(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn palette_overrides_preset() {
+		let mut gui = Gui {
+			color: ColorVariant::colorblind,
+			..Gui::default()
+		};
+		gui.theme.heading = ThemeColor::ansi(208);
+		let palette = gui.palette();
+		assert_eq!(palette.temperature, ThemeColor::rgb(230, 159, 0));
+		assert_eq!(palette.heading, ThemeColor::ansi(208));
+		assert_eq!(palette.wind, ThemeColor::rgb(0, 158, 115));
+
+		gui.color = ColorVariant::plain;
+		assert_eq!(gui.palette().border, ThemeColor::none);
+		assert_eq!(gui.palette().heading, ThemeColor::ansi(208));
+	}
+
+	#[test]
+	fn gradient_from_cold_to_hot() {
+		let gui = Gui::default();
+		assert_eq!(gui.gradient(-30.0), ThemeColor::rgb(80, 120, 255));
+		assert_eq!(gui.gradient(5.0), ThemeColor::rgb(0, 195, 188));
+		assert_eq!(gui.gradient(50.0), ThemeColor::rgb(220, 30, 30));
+
+		let plain = Gui {
+			color: ColorVariant::plain,
+			..Gui::default()
+		};
+		assert_eq!(plain.gradient(20.0), ThemeColor::none);
+	}
+
+	#[test]
+	fn temperature_color_of_theme() {
+		let mut gui = Gui {
+			color: ColorVariant::colorblind,
+			..Gui::default()
+		};
+		assert_eq!(gui.temperature_color(-5.0), gui.palette().temperature);
+		assert_eq!(gui.temperature_color(35.0), gui.palette().temperature);
+
+		gui.theme.temperature = ThemeColor::ansi(45);
+		assert_eq!(gui.temperature_color(20.0), ThemeColor::ansi(45));
+
+		gui.gradient = true;
+		assert_eq!(gui.temperature_color(-30.0), ThemeColor::rgb(0, 114, 178));
+	}
+
+	#[test]
+	fn color_conversions() {
+		assert_eq!(to_ansi256(255, 135, 0), 208);
+		assert_eq!(from_ansi256(208), (255, 135, 0));
+		assert_eq!(ThemeColor::yellow.hex().as_deref(), Some("#b58900"));
+		assert_eq!(ThemeColor::rgb(230, 159, 0).hex().as_deref(), Some("#e69f00"));
+		assert_eq!(ThemeColor::preset.hex(), None);
+	}
 }
@@ -1,142 +1,152 @@
 use anyhow::Result;
 use chrono::NaiveDate;
-use dialoguer::console::style;
-use unicode_width::UnicodeWidthStr;
 
-use crate::modules::{
-	display::hourly::WIDTH,
-	localization::Locales,
-	params::Params,
-	units::{Precipitation, Time},
-};
+use crate::modules::{localization::Locales, params::Params, sun::Daylight, units::Precipitation};
 
 use super::{
 	border::{Border, BorderStyle, Edge, Separator},
 	gui_config::ConfigurableColor,
 	hourly,
+	icons::Icon,
 	product::{Product, TOTAL_BORDER_PADDING},
-	utils::pad_string_to_width,
+	utils::{align_cells, display_width, format_sun_times, is_rtl, mirror_cells, pad_cell},
 	weathercode::WeatherCode,
 };
 
 #[allow(clippy::too_many_lines)]
 pub fn prep(product: &Product, params: &Params, date: NaiveDate) -> Result<Vec<String>> {
-	let address = Product::trunc_address(product.address.clone(), 60);
+	let width = params.layout.width();
+	let address = Product::trunc_address(product.address.clone(), width - 12);
 
 	// Helpers
 	let weather = &product.historical_weather[&date];
-	let weather_daily_units = weather.daily_units.as_ref().unwrap();
 	let lang = &params.config.language;
-	// Times
-	let sunrise = weather.daily.sunrise.as_ref().unwrap();
-	let sunset = weather.daily.sunset.as_ref().unwrap();
-	let (sunrise_hour, sunset_hour) = (
-		sunrise[0][11..13].parse::<usize>().unwrap_or_default(),
-		sunset[0][11..13].parse::<usize>().unwrap_or_default(),
-	);
 
 	// Display Items
-	let sunrise = match params.config.units.time {
-		Time::am_pm => format!("{}:{}am", sunrise_hour, &sunrise[0][14..16]),
-		Time::military => sunrise[0][11..16].to_string(),
-	};
-	let sunset = match params.config.units.time {
-		Time::am_pm => format!("{}:{}pm", sunset_hour - 12, &sunset[0][14..16]),
-		Time::military => sunset[0][11..16].to_string(),
-	};
+	let (sunrise, sunset) = format_sun_times(weather.sun_times(), params.config.units.time)?;
 	let temp_max_min = format!(
-		"{:.1}/{:.1}{}",
-		weather.daily.temperature_2m_max.as_ref().unwrap()[0],
-		weather.daily.temperature_2m_min.as_ref().unwrap()[0],
-		weather_daily_units.temperature_2m_max,
+		"{}/{}{}",
+		params.format_decimal(weather.daily.temperature_2m_max.as_ref().unwrap()[0]),
+		params.format_decimal(weather.daily.temperature_2m_min.as_ref().unwrap()[0]),
+		params.temperature_unit(),
 	);
 	let apparent_temp_max_min = format!(
-		"{} {:.1}/{:.1}{}",
+		"{} {}/{}{}",
 		params.texts.weather.felt_like,
-		weather.daily.apparent_temperature_max.as_ref().unwrap()[0],
-		weather.daily.apparent_temperature_min.as_ref().unwrap()[0],
-		weather_daily_units.temperature_2m_max,
+		params.format_decimal(weather.daily.apparent_temperature_max.as_ref().unwrap()[0]),
+		params.format_decimal(weather.daily.apparent_temperature_min.as_ref().unwrap()[0]),
+		params.temperature_unit(),
 	);
+	let icons = params.config.gui.icons;
 	let precipitation_sum = format!(
-		"❲{}{}❳",
-		weather.daily.precipitation_sum.as_ref().unwrap()[0],
-		if params.config.units.precipitation == Precipitation::inch {
-			"ᵢₙ"
+		"{}{}{}{}{}",
+		icons.get(Icon::SummaryStart),
+		params.localize_decimal(&weather.daily.precipitation_sum.as_ref().unwrap()[0].to_string()),
+		icons.get(Icon::Precipitation),
+		icons.get(if params.config.units.precipitation == Precipitation::inch {
+			Icon::Inches
 		} else {
-			"ₘₘ"
-		}
+			Icon::Millimeters
+		}),
+		icons.get(Icon::SummaryEnd)
 	);
 	let date = format!(
-		" {}",
+		"{} {}",
+		icons.get(Icon::Calendar),
 		if lang == "en_US" || lang == "en" {
 			date.format("%a, %-d %b %Y").to_string()
 		} else {
 			Locales::localize_date(date, lang)?
 		}
 	);
-	let sunrise = format!(" {sunrise}");
-	let sunset = format!(" {sunset}");
+	let sunrise = format!("{} {sunrise}", icons.get(Icon::Sunrise));
+	let sunset = format!("{} {sunset}", icons.get(Icon::Sunset));
 	let wmo_code = WeatherCode::resolve(
 		weather.daily.weathercode.as_ref().unwrap()[0],
-		false,
+		Daylight::Day,
 		&params.texts.weather.weather_code,
+		&params.config.gui,
 	)?;
 
-	let gui = &params.config.gui;
-	let width_no_border_pad = WIDTH - TOTAL_BORDER_PADDING;
+	let (gui, rtl) = (&params.config.gui, is_rtl(&params.config.language));
+	let width_no_border_pad = width - TOTAL_BORDER_PADDING;
 
 	let mut result = Vec::<String>::new();
 
 	// Border Top
-	result.push(format!(
-		"{}",
-		&Edge::Top.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", &Edge::Top.fmt(width, gui.border).border_color(gui)));
 
 	// Address / Title
 	result.push(format!(
 		"{} {} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		style(pad_string_to_width(&address, width_no_border_pad)).bold(),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		Border::L.fmt(gui.border).border_color(gui),
+		align_cells(&[(&address, width_no_border_pad)], rtl)
+			.as_str()
+			.heading_color(gui)
+			.bold(),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Separator
 	result.push(format!(
 		"{}",
 		&match gui.border {
-			BorderStyle::double => Separator::Double.fmt(WIDTH, gui.border),
-			BorderStyle::solid => Separator::Solid.fmt(WIDTH, gui.border),
-			_ => Separator::Single.fmt(WIDTH, gui.border),
+			BorderStyle::double => Separator::Double.fmt(width, gui.border),
+			BorderStyle::solid => Separator::Solid.fmt(width, gui.border),
+			_ => Separator::Single.fmt(width, gui.border),
 		}
-		.plain_or_bright_black(gui.color)
+		.border_color(gui)
 	));
 
 	// Temperature & Weathercode
-	let temperature_and_weathercode = format!(
+	let mut temperature_and_weathercode = format!(
 		"{} {}, {} {}",
 		wmo_code.icon, wmo_code.interpretation, temp_max_min, precipitation_sum
 	);
+	// The icon stands in for the interpretation if the row doesn't fit into a condensed layout.
+	if display_width(&temperature_and_weathercode) + display_width(&date) > width_no_border_pad {
+		temperature_and_weathercode = format!("{} {} {}", wmo_code.icon, temp_max_min, precipitation_sum);
+	}
 	result.push(format!(
-		"{} {}{} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		style(pad_string_to_width(
-			&temperature_and_weathercode,
-			width_no_border_pad - date.width()
-		))
-		.bold(),
-		date,
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		"{} {} {}",
+		Border::L.fmt(gui.border).border_color(gui),
+		mirror_cells(
+			&[
+				params.paint_temperatures(
+					&pad_cell(&temperature_and_weathercode, width_no_border_pad - display_width(&date), rtl),
+					&[
+						weather.daily.temperature_2m_max.as_ref().unwrap()[0],
+						weather.daily.temperature_2m_min.as_ref().unwrap()[0]
+					],
+					true
+				),
+				date,
+			],
+			rtl
+		),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Apparent Temperature & Sun Rise & Sun Set
 	let sunrise_and_sunset = format!("{sunrise}  {sunset}");
 	result.push(format!(
-		"{} {}{} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		pad_string_to_width(&apparent_temp_max_min, width_no_border_pad - sunrise_and_sunset.width()),
-		sunrise_and_sunset,
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		"{} {} {}",
+		Border::L.fmt(gui.border).border_color(gui),
+		params.paint_temperatures(
+			&align_cells(
+				&[
+					(&apparent_temp_max_min, width_no_border_pad - display_width(&sunrise_and_sunset)),
+					(&sunrise_and_sunset, display_width(&sunrise_and_sunset)),
+				],
+				rtl
+			),
+			&[
+				weather.daily.apparent_temperature_max.as_ref().unwrap()[0],
+				weather.daily.apparent_temperature_min.as_ref().unwrap()[0]
+			],
+			false
+		),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Hourly Overview
@@ -145,10 +155,7 @@ pub fn prep(product: &Product, params: &Params, date: NaiveDate) -> Result<Vec<S
 	}
 
 	// Border Bottom
-	result.push(format!(
-		"{}",
-		Edge::Bottom.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", Edge::Bottom.fmt(width, gui.border).border_color(gui)));
 
 	Ok(result)
 }
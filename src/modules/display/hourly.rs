@@ -1,264 +1,494 @@
 use anyhow::Result;
 use chrono::{NaiveDateTime, Timelike};
-use dialoguer::console::style;
+use dialoguer::console::{style, Term};
+use optional_struct::optional_struct;
+use serde::{Deserialize, Serialize};
 use std::fmt::Write as _;
 
 use crate::modules::{
-	localization::WeatherCodeLocales,
 	params::Params,
-	units::{Precipitation, Temperature, Time},
-	weather::OptionalWeather,
+	sun::Daylight,
+	units::{Precipitation, Time},
+	weather::{hour_of, hours_of_day, OptionalWeather},
 };
 
 use super::{
 	border::{Border, BorderStyle, Separator},
-	graph::Graph,
+	graph::{Graph, Series, Stacking},
 	gui_config::ConfigurableColor,
+	icons::Icon,
 	product::{Product, TOTAL_BORDER_PADDING},
-	utils::{pad_string_to_width, style_number},
+	utils::{align_cells, display_width, is_rtl, pad_string_to_width, style_number},
 	weathercode::WeatherCode,
 };
 
-const DISPLAY_HOURS: [usize; 8] = [0, 3, 6, 9, 12, 15, 18, 21];
-pub const WIDTH: usize = 72;
+#[optional_struct(ConfigFileHourlyOpts)]
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct HourlyOpts {
+	#[optional_serde_skip_none]
+	pub window: Window,
+	#[optional_serde_skip_none]
+	pub label_step: LabelStep,
+}
+
+// Hours of today's hourly forecast.
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum Window {
+	#[default]
+	day,
+	// The given number of hours from the current one on, across midnight
+	next(u8),
+}
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum LabelStep {
+	// Picked by the columns available for each hour
+	#[default]
+	auto,
+	hours(u8),
+}
+
+// Layouts of the hourly forecast, picked by the width of the terminal.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Layout {
+	Condensed,
+	#[default]
+	Regular,
+	Wide,
+}
+
+impl Layout {
+	// The widest layout whose box, including its borders, fits into the terminal.
+	pub fn fit(terminal_width: usize) -> Self {
+		[Self::Wide, Self::Regular]
+			.into_iter()
+			.find(|layout| layout.width() + TOTAL_BORDER_PADDING <= terminal_width)
+			.unwrap_or(Self::Condensed)
+	}
+
+	pub fn detect(width: Option<usize>) -> Self {
+		match width.or_else(|| Term::stdout().size_checked().map(|(_, cols)| cols.into())) {
+			Some(width) => Self::fit(width),
+			// Output that isn't written to a terminal keeps the regular layout.
+			None => Self::default(),
+		}
+	}
+
+	// Width of the graph and of the boxes around it, without borders. A whole day takes 2, 3 or 8 columns per hour.
+	pub const fn width(self) -> usize {
+		match self {
+			Self::Condensed => 48,
+			Self::Regular => 72,
+			Self::Wide => 192,
+		}
+	}
+}
+
+// Columns needed by a label with its time, and by a temperature with its icon.
+const LABEL_WIDTH: usize = 8;
+const MIN_LABEL_WIDTH: usize = 3;
+
+// The hours of an hourly forecast and how they are spread over the width of a layout.
+struct Frame {
+	// Index of the first hour in the hourly values
+	start: usize,
+	hours: usize,
+	cols_per_hour: usize,
+	label_step: usize,
+	// Hour of the day of each displayed hour, taken from the timestamps as days can be shorter or longer than 24 hours
+	clock: Vec<usize>,
+}
+
+impl Frame {
+	fn new(layout: Layout, label_step: LabelStep, times: &[String], start: usize, hours: usize) -> Self {
+		// The graph needs at least two columns per hour.
+		let hours = hours.clamp(1, layout.width() / 2);
+		let cols_per_hour = layout.width() / hours;
+		let label_step = match label_step {
+			LabelStep::auto => [1, 2, 3, 6]
+				.into_iter()
+				.find(|step| step * cols_per_hour >= LABEL_WIDTH)
+				.unwrap_or(6),
+			// Steps that are too narrow for the temperatures are widened.
+			LabelStep::hours(step) => usize::from(step).max(MIN_LABEL_WIDTH.div_ceil(cols_per_hour)),
+		};
+
+		Self {
+			start,
+			hours,
+			cols_per_hour,
+			label_step,
+			clock: (start..start + hours)
+				.map(|i| times.get(i.min(times.len().saturating_sub(1))).map_or(0, |time| hour_of(time)))
+				.collect(),
+		}
+	}
+
+	// Offsets of the labelled hours from the first one.
+	fn labelled_hours(&self) -> impl Iterator<Item = usize> {
+		(0..self.hours).step_by(self.label_step)
+	}
+
+	fn hour_of_day(&self, offset: usize) -> usize {
+		self.clock[offset]
+	}
+
+	// Columns of one labelled hour and the hours until the next label.
+	const fn slot_width(&self) -> usize {
+		self.label_step * self.cols_per_hour
+	}
+
+	// Column of the current time in a separator of the given width. Late in the last hour of a short window it would
+	// pass the right border, so it stays inside.
+	fn time_indicator_col(&self, hour_index: usize, minute: u32, width: usize) -> usize {
+		let col_adjustment = match hour_index.checked_sub(self.start) {
+			// if it's the last hour of the day, the time idicator will be placed at the beginning of the graph
+			None => 1,
+			// add 3 cols to adjust to the multiple chars used to display the current hour below the chart
+			Some(offset) => offset * self.cols_per_hour + 3,
+		};
+		(col_adjustment + minute as usize * self.cols_per_hour / 60).min(width - 1)
+	}
+
+	// Values of the displayed hours plus the following one, which the graph connects to.
+	// Hours past the end of the forecast repeat its last value.
+	fn slice<T: Copy>(&self, values: &[T]) -> Vec<T> {
+		(self.start..=self.start + self.hours)
+			.map(|i| values[i.min(values.len() - 1)])
+			.collect()
+	}
+}
 
 struct WeatherSummary {
 	temp_max_min: String,
 	precipitation_probability_max: u8,
 }
 
-#[allow(clippy::too_many_lines)]
 pub fn prep(product: &Product, params: &Params, day_index: usize) -> Result<Vec<String>> {
 	let weather = &product.weather;
 	let current_dt = NaiveDateTime::parse_from_str(&product.weather.current_weather.time, "%Y-%m-%dT%H:%M")?;
-	let current_hour = current_dt.hour() as usize;
-
-	// The graph splits one hour into three "levels": last, current and next.
-	// We slice 25 items to use the 25th in the last "next"-level of a graph.
-	let day_start_idx = day_index * 24;
-	let day_end_idx = day_start_idx + 24;
-	let next_day_start_idx = day_end_idx + 1;
-	let next_day_end_idx = next_day_start_idx + 24;
-
-	let (temperatures, weather_codes, precipitation): (&[f32], &[u8], Vec<u8>);
-	let (mut temps, mut codes, mut prec);
-	// If it's the last possible requested day, the last index(start_index of the 7th day) is not available.
-	// Therefore we'll extend the values by 1. For this we simply use the last value of the array twice.
-	if day_index == 6 {
-		temperatures = {
-			temps = weather.hourly.temperature_2m[day_start_idx..].to_vec();
-			temps.push(weather.hourly.temperature_2m[weather.hourly.temperature_2m.len() - 1]);
-			&temps
-		};
-		weather_codes = {
-			codes = weather.hourly.weathercode[day_start_idx..].to_vec();
-			codes.push(weather.hourly.weathercode[weather.hourly.weathercode.len() - 1]);
-			&codes
-		};
-		precipitation = match params.config.units.precipitation {
-			Precipitation::probability => {
-				prec = weather.hourly.precipitation_probability[day_start_idx..].to_vec();
-				prec.push(weather.hourly.precipitation_probability[weather.hourly.precipitation_probability.len() - 1]);
-				prec
-			}
-			_ => weather.hourly.precipitation[day_start_idx..]
-				.iter()
-				.map(|x| x.ceil() as u8)
-				.collect::<Vec<u8>>(),
-		};
-	// If it's the end of one day we show the weather of the next day
-	} else if current_hour == day_end_idx - 1 {
-		temperatures = &weather.hourly.temperature_2m[next_day_start_idx..=next_day_end_idx];
-		weather_codes = &weather.hourly.weathercode[next_day_start_idx..=next_day_end_idx];
-		precipitation = match params.config.units.precipitation {
-			Precipitation::probability => {
-				weather.hourly.precipitation_probability[next_day_start_idx..=next_day_end_idx].to_vec()
-			}
-			_ => weather.hourly.precipitation[next_day_start_idx..=next_day_end_idx]
-				.iter()
-				.map(|x| x.ceil() as u8)
-				.collect::<Vec<u8>>(),
-		};
-	} else {
-		temperatures = &weather.hourly.temperature_2m[day_start_idx..=day_end_idx];
-		weather_codes = &weather.hourly.weathercode[day_start_idx..=day_end_idx];
-		precipitation = match params.config.units.precipitation {
-			Precipitation::probability => {
-				weather.hourly.precipitation_probability[day_start_idx..=day_end_idx].to_vec()
-			}
-			_ => weather.hourly.precipitation[day_start_idx..=day_end_idx]
-				.iter()
-				.map(|x| x.ceil() as u8)
-				.collect::<Vec<u8>>(),
-		};
+	let (today, hour_index) = (day_index == weather.past_days, weather.current_hour_index());
+
+	let (layout, opts) = (params.layout, params.config.gui.hourly);
+	let (times, day) = (&weather.hourly.time, weather.hours_of_day(day_index));
+	let frame = match (today, opts.window) {
+		(true, Window::next(hours)) => Frame::new(layout, opts.label_step, times, hour_index, hours.into()),
+		// If it's the end of one day we show the weather of the next day
+		(true, Window::day) if hour_index + 1 >= day.end && day_index + 1 < weather.daily.time.len() => {
+			let next_day = weather.hours_of_day(day_index + 1);
+			Frame::new(layout, opts.label_step, times, next_day.start, next_day.len())
+		}
+		_ => Frame::new(layout, opts.label_step, times, day.start, day.len()),
+	};
+	let width = layout.width();
+	let heading = match (today, opts.window) {
+		(true, Window::next(_)) => params.texts.weather.next_hours.select(frame.hours, &params.config.language),
+		_ => params.texts.weather.hourly_forecast.clone(),
 	};
 
-	let time_indicator_col = if day_index == 0 && params.config.gui.graph.time_indicator {
-		let col_adjustment = if current_hour == day_end_idx - 1 {
-			// if it's the last hour of the day, the time idicator will be placed at the beginning of the graph
-			1
-		} else {
-			// add 3 cols to adjust to the multiple chars used to display the current hour below the chart
-			(current_hour * 3) + 3
-		};
-		Some(col_adjustment + (current_dt.minute() / 20) as usize)
+	let temperatures = frame.slice(&weather.hourly.temperature_2m);
+	let weather_codes = frame.slice(&weather.hourly.weathercode);
+	let series = params
+		.config
+		.gui
+		.graph
+		.series
+		.iter()
+		.map(|&series| {
+			let hourly = &weather.hourly;
+			let values = match series {
+				Series::temperature => &hourly.temperature_2m,
+				Series::precipitation => &hourly.precipitation,
+				Series::wind => &hourly.windspeed_10m,
+				Series::humidity => &hourly.relativehumidity_2m,
+				Series::pressure => &hourly.surface_pressure,
+				Series::uv_index => &hourly.uv_index,
+			};
+			(series, frame.slice(values))
+		})
+		.collect::<Vec<_>>();
+	let precipitation = match params.config.units.precipitation {
+		Precipitation::probability => frame.slice(&weather.hourly.precipitation_probability),
+		_ => frame
+			.slice(&weather.hourly.precipitation)
+			.iter()
+			.map(|x| x.ceil() as u8)
+			.collect::<Vec<u8>>(),
+	};
+
+	let time_indicator_col = if today && params.config.gui.graph.time_indicator {
+		Some(frame.time_indicator_col(hour_index, current_dt.minute(), width))
 	} else {
 		None
 	};
 
-	let sunrise_sunset = (
-		weather.daily.sunrise[day_index][11..13].parse::<usize>().unwrap_or_default(),
-		weather.daily.sunset[day_index][11..13].parse::<usize>().unwrap_or_default(),
-	);
+	let daylight = frame.slice(&times.iter().map(|time| weather.daylight(time)).collect::<Vec<_>>());
 
 	// Future or historical forecast already include a weather Max/Min summary in the top part of the display.
-	let summary = match day_index {
-		0 => Some(WeatherSummary {
+	let summary = match (today, opts.window) {
+		// The summary of a window covers its hours instead of the day.
+		(true, Window::next(_)) => {
+			let window = &temperatures[..frame.hours];
+			Some(WeatherSummary {
+				temp_max_min: format!(
+					"{}/{}{}",
+					params.format_decimal(window.iter().copied().fold(f32::NEG_INFINITY, f32::max)),
+					params.format_decimal(window.iter().copied().fold(f32::INFINITY, f32::min)),
+					params.temperature_unit(),
+				),
+				precipitation_probability_max: frame.slice(&weather.hourly.precipitation_probability)[..frame.hours]
+					.iter()
+					.copied()
+					.max()
+					.unwrap_or_default(),
+			})
+		}
+		(true, _) => Some(WeatherSummary {
 			temp_max_min: format!(
-				"{:.1}/{:.1}{}",
-				weather.daily.temperature_2m_max[day_index],
-				weather.daily.temperature_2m_min[day_index],
-				weather.daily_units.temperature_2m_max,
+				"{}/{}{}",
+				params.format_decimal(weather.daily.temperature_2m_max[day_index]),
+				params.format_decimal(weather.daily.temperature_2m_min[day_index]),
+				params.temperature_unit(),
 			),
 			precipitation_probability_max: weather.daily.precipitation_probability_max[day_index],
 		}),
 		_ => None,
 	};
 
+	render(
+		&HourlyView {
+			heading,
+			summary,
+			temperatures,
+			weather_codes,
+			daylight,
+			series,
+			precipitation,
+			precipitation_unit: params.config.units.precipitation,
+			time_indicator_col,
+		},
+		&frame,
+		params,
+	)
+}
+
+// The values of the hours that `prep` and `prep_historical` put into the same box.
+struct HourlyView {
+	heading: String,
+	summary: Option<WeatherSummary>,
+	temperatures: Vec<f32>,
+	weather_codes: Vec<u8>,
+	daylight: Vec<Daylight>,
+	series: Vec<(Series, Vec<f32>)>,
+	precipitation: Vec<u8>,
+	precipitation_unit: Precipitation,
+	time_indicator_col: Option<usize>,
+}
+
+fn render(view: &HourlyView, frame: &Frame, params: &Params) -> Result<Vec<String>> {
 	let (units, gui) = (&params.config.units, &params.config.gui);
-	let width_no_border_pad = WIDTH - TOTAL_BORDER_PADDING;
+	let width = params.layout.width();
+	let width_no_border_pad = width - TOTAL_BORDER_PADDING;
 
 	let mut result = Vec::<String>::new();
 
 	// Blank Line
-	result.push(format!(
-		"{}",
-		&Separator::Blank.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color),
-	));
+	result.push(format!("{}", &Separator::Blank.fmt(width, gui.border).border_color(gui)));
 
 	// Set Measurement Unit Symbols
-	let temperature_unit = match units.temperature {
-		Temperature::fahrenheit => "",
-		Temperature::celsius => "",
-	};
-	let precipitation_unit = match units.precipitation {
-		Precipitation::mm => "ₘₘ",
-		Precipitation::inch => "ᵢₙ",
-		Precipitation::probability => "󰖎 ",
+	let temperature_unit = gui.icons.temperature(units.temperature);
+	let precipitation_unit = match view.precipitation_unit {
+		Precipitation::mm => format!("{}{}", gui.icons.get(Icon::Precipitation), gui.icons.get(Icon::Millimeters)),
+		Precipitation::inch => format!("{}{}", gui.icons.get(Icon::Precipitation), gui.icons.get(Icon::Inches)),
+		Precipitation::probability => pad_string_to_width(gui.icons.get(Icon::Probability), 2),
 	};
 
 	// Hourly Forecast Heading
 	result.push(format!(
 		"{} {} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		style(pad_string_to_width(&params.texts.weather.hourly_forecast, width_no_border_pad)).bold(),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+		Border::L.fmt(gui.border).border_color(gui),
+		align_cells(&[(&view.heading, width_no_border_pad)], is_rtl(&params.config.language))
+			.as_str()
+			.heading_color(gui)
+			.bold(),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Day Max/Mix Temperature + Max Precipitation
-	if let Some(summary) = summary {
+	if let Some(summary) = &view.summary {
 		result.push(format!(
-			"{} {} ❲{}{}❳{: <WIDTH$} {}",
-			Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
+			"{} {} {}{}{}{}{: <WIDTH$} {}",
+			Border::L.fmt(gui.border).border_color(gui),
 			summary.temp_max_min,
+			gui.icons.get(Icon::SummaryStart),
 			summary.precipitation_probability_max,
-			style("󰖎").bold(),
+			style(gui.icons.get(Icon::Probability)).bold(),
+			gui.icons.get(Icon::SummaryEnd),
 			"",
-			Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-			WIDTH = WIDTH - 5 - summary.temp_max_min.len() - summary.precipitation_probability_max.to_string().len()
+			Border::R.fmt(gui.border).border_color(gui),
+			WIDTH = width
+				- 5 - display_width(&summary.temp_max_min)
+				- display_width(gui.icons.get(Icon::Probability))
+				- summary.precipitation_probability_max.to_string().len()
 		));
 	}
 
 	// Graph Border Top with Potential Time Indicator
-	match time_indicator_col {
-		Some(col) => result.push(format!(
-			"{}",
-			prepare_separator(col, gui.border, WIDTH, '╤').plain_or_bright_black(gui.color)
-		)),
-		_ => result.push(format!(
-			"{}",
-			Separator::Dashed.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-		)),
-	};
+	match view.time_indicator_col {
+		Some(col) => result.push(format!("{}", prepare_separator(col, gui.border, width, '╤').border_color(gui))),
+		_ => result.push(format!("{}", Separator::Dashed.fmt(width, gui.border).border_color(gui))),
+	}
 
 	// Temperatures
-	let temps = prepare_temperatures(temperatures, weather_codes, sunrise_sunset, &params.texts.weather.weather_code)?;
+	let temps = prepare_temperatures(&view.temperatures, &view.weather_codes, &view.daylight, params, frame)?;
 	result.push(format!(
-		"{} {: <WIDTH$}{} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		temps.plain_or_yellow(gui.color).bold(),
-		temperature_unit.plain_or_yellow(gui.color).bold(),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-		WIDTH = WIDTH - 3
+		"{} {}{} {}",
+		Border::L.fmt(gui.border).border_color(gui),
+		paint_temperatures(&temps, params, width - 3),
+		temperature_unit.temperature_color(gui).bold(),
+		Border::R.fmt(gui.border).border_color(gui),
 	));
 
 	// Blank Line
-	result.push(format!(
-		"{}",
-		&Separator::Blank.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", &Separator::Blank.fmt(width, gui.border).border_color(gui)));
 
-	let graph = Graph::prepare_graph(temperatures, &params.config.gui.graph);
-	// Graph Row 1
-	if graph.1.chars().count() > 0 {
-		result.push(format!(
-			"{}{}{}",
-			Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-			graph.1.plain_or_yellow(gui.color),
-			Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-		));
-	}
-	// Graph Row 2
-	result.push(format!(
-		"{}{}{}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		graph.0.plain_or_yellow(gui.color),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-	));
+	// Graph Rows
+	result.extend(prepare_graphs(&view.series, params.temperature_unit(), params, frame));
 
 	// Precipitation
-	let precipitation = prepare_precipitation(&precipitation);
+	let precipitation = prepare_precipitation(&view.precipitation, frame);
 	result.push(format!(
 		"{} {: <WIDTH$}{}{}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		precipitation.plain_or_blue(gui.color).bold(),
-		if units.precipitation == Precipitation::probability {
+		Border::L.fmt(gui.border).border_color(gui),
+		precipitation.precipitation_color(gui).bold(),
+		if view.precipitation_unit == Precipitation::probability {
 			// to enlarge the water percent icon we use bold as a hack
-			precipitation_unit.plain_or_blue(gui.color).bold()
+			precipitation_unit.as_str().precipitation_color(gui).bold()
 		} else {
-			precipitation_unit.plain_or_blue(gui.color)
+			precipitation_unit.as_str().precipitation_color(gui)
 		},
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-		WIDTH = WIDTH - 1 - precipitation_unit.chars().count()
+		Border::R.fmt(gui.border).border_color(gui),
+		WIDTH = width - 1 - display_width(&precipitation_unit)
 	));
 
 	// Graph Border Bottom with Potential Time Indicator
-	match time_indicator_col {
-		Some(col) => result.push(format!(
-			"{}",
-			prepare_separator(col, gui.border, WIDTH, '╧').plain_or_bright_black(gui.color)
-		)),
-		_ => result.push(format!(
-			"{}",
-			Separator::Dashed.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-		)),
-	};
+	match view.time_indicator_col {
+		Some(col) => result.push(format!("{}", prepare_separator(col, gui.border, width, '╧').border_color(gui))),
+		_ => result.push(format!("{}", Separator::Dashed.fmt(width, gui.border).border_color(gui))),
+	}
 
 	// Graph Hours Row
-	let mut hours_row = format!("{}", Border::L.fmt(gui.border).plain_or_bright_black(gui.color));
-	let hours = match units.time {
-		Time::am_pm => ["¹²˙⁰⁰ₐₘ", "³˙⁰⁰ₐₘ", "⁶˙⁰⁰ₐₘ", "⁹˙⁰⁰ₐₘ", "¹²˙⁰⁰ₚₘ", "³˙⁰⁰ₚₘ", "⁶˙⁰⁰ₚₘ", "⁹˙⁰⁰ₚₘ"],
-		Time::military => ["⁰⁰˙⁰⁰", "⁰³˙⁰⁰", "⁰⁶˙⁰⁰", "⁰⁹˙⁰⁰", "¹²˙⁰⁰", "¹⁵˙⁰⁰", "¹⁸˙⁰⁰", "²¹˙⁰⁰"],
-	};
-	for hour in hours {
-		hours_row.push_str(&format!("{hour: <9}"));
-	}
-	hours_row.push_str(&format!("{}", Border::R.fmt(gui.border).plain_or_bright_black(gui.color)));
+	let mut hours_row = format!("{}", Border::L.fmt(gui.border).border_color(gui));
+	hours_row.push_str(&prepare_hours(units.time, frame));
+	let _ = write!(hours_row, "{}", Border::R.fmt(gui.border).border_color(gui));
 	result.push(hours_row);
 
 	Ok(result)
 }
 
+// Rows of the graphs of each series. Unless only the temperature is plotted, each series is captioned with its
+// name and range.
+fn prepare_graphs(
+	series: &[(Series, Vec<f32>)],
+	temperature_unit: &str,
+	params: &Params,
+	frame: &Frame,
+) -> Vec<String> {
+	let (units, gui, texts) = (&params.config.units, &params.config.gui, &params.texts);
+	let width = params.layout.width();
+	let rtl = is_rtl(&params.config.language);
+	let (border_l, border_r) = (
+		Border::L.fmt(gui.border).border_color(gui).to_string(),
+		Border::R.fmt(gui.border).border_color(gui).to_string(),
+	);
+
+	let graphs = series
+		.iter()
+		.map(|(series, values)| {
+			let graph = match series {
+				Series::precipitation => Graph::prepare_bars(values, gui.graph.rowspan, frame.cols_per_hour),
+				_ => Graph::prepare_graph(values, &gui.graph, frame.cols_per_hour),
+			};
+			let upper = if graph.1.is_empty() { graph.1 } else { pad_string_to_width(&graph.1, width) };
+			(*series, Graph(pad_string_to_width(&graph.0, width), upper))
+		})
+		.collect::<Vec<_>>();
+	// Temperatures along the gradient take the value of the hour each column belongs to.
+	let temperatures = series
+		.iter()
+		.find_map(|(series, values)| (*series == Series::temperature).then_some(values));
+	let color = |series: Series, col: usize| match temperatures {
+		Some(values) if gui.gradient && series == Series::temperature => {
+			let value = values[(col / frame.cols_per_hour).min(values.len() - 1)];
+			gui.gradient(units.temperature.to_celsius(value))
+		}
+		_ => series.color(gui),
+	};
+	let rows = |graphs: &[(Series, Graph)]| {
+		Graph::combine(graphs, gui.graph.stacking, color)
+			.into_iter()
+			.map(|row| format!("{border_l}{row}{border_r}"))
+			.collect::<Vec<_>>()
+	};
+
+	if matches!(series, [(Series::temperature, _)]) {
+		return rows(&graphs);
+	}
+
+	let captions = series
+		.iter()
+		.map(|(series, values)| {
+			let hours = &values[..frame.hours];
+			let unit = match series {
+				Series::temperature => temperature_unit,
+				Series::precipitation => texts.units.precipitation(units.precipitation),
+				Series::wind => texts.units.speed(units.speed),
+				Series::humidity => "%",
+				Series::pressure => texts.units.pressure(units.pressure),
+				Series::uv_index => "",
+			};
+			let caption = format!(
+				"{} {}–{}{}",
+				texts.weather.series.name(*series),
+				params.format_decimal(hours.iter().copied().fold(f32::INFINITY, f32::min)),
+				params.format_decimal(hours.iter().copied().fold(f32::NEG_INFINITY, f32::max)),
+				unit
+			);
+			(*series, caption)
+		})
+		.collect::<Vec<_>>();
+	let caption_row = |captions: &[(Series, String)]| {
+		let plain = captions.iter().map(|(_, c)| c.as_str()).collect::<Vec<_>>().join("  ");
+		let painted = captions
+			.iter()
+			.map(|(series, c)| series.paint(c, gui))
+			.collect::<Vec<_>>()
+			.join("  ");
+		let padding = " ".repeat((width - TOTAL_BORDER_PADDING).saturating_sub(display_width(&plain)));
+		let caption = if rtl { padding + &painted } else { painted + &padding };
+		format!("{border_l} {caption} {border_r}")
+	};
+
+	match gui.graph.stacking {
+		Stacking::stacked => graphs
+			.into_iter()
+			.zip(&captions)
+			.flat_map(|(graph, caption)| {
+				let mut result = vec![caption_row(std::slice::from_ref(caption))];
+				result.extend(rows(&[graph]));
+				result
+			})
+			.collect(),
+		Stacking::overlay => {
+			let mut result = vec![caption_row(&captions)];
+			result.extend(rows(&graphs));
+			result
+		}
+	}
+}
+
 fn prepare_separator(
 	time_indicator_col: usize,
 	border_variant: BorderStyle,
@@ -287,172 +517,216 @@ fn prepare_separator(
 	}
 }
 
+// Labelled temperatures and their weather icons, each with the temperature it shows.
 fn prepare_temperatures(
 	temperatures: &[f32],
 	weather_codes: &[u8],
-	sunrise_sunset: (usize, usize),
-	t: &WeatherCodeLocales,
-) -> Result<String> {
-	let mut result = String::new();
-
-	for hour in DISPLAY_HOURS {
-		let temp = temperatures[hour].round() as i32;
+	daylight: &[Daylight],
+	params: &Params,
+	frame: &Frame,
+) -> Result<Vec<(f32, String)>> {
+	let mut result = Vec::new();
+
+	for offset in frame.labelled_hours() {
+		let temp = temperatures[offset].round() as i32;
 		let temp_sub = style_number(temp, true);
-		let night = hour < sunrise_sunset.0 || hour > sunrise_sunset.1;
-		let wmo_code = WeatherCode::resolve(weather_codes[hour], night, t)?;
-		let colspan = if hour == 0 { 2 } else { 8 };
-		let _ = write!(result, "{: >colspan$}{}", temp_sub, wmo_code.icon);
+		let wmo_code = WeatherCode::resolve(
+			weather_codes[offset],
+			daylight[offset],
+			&params.texts.weather.weather_code,
+			&params.config.gui,
+		)?;
+		// Wide icons take the place of the padding.
+		let colspan = if offset == 0 { 2 } else { frame.slot_width() - 1 };
+		let colspan = (colspan + 1).saturating_sub(display_width(&wmo_code.icon));
+		result.push((temperatures[offset], format!("{: >colspan$}{}", temp_sub, wmo_code.icon)));
 	}
 
 	Ok(result)
 }
 
-fn prepare_precipitation(precipitation: &[u8]) -> String {
+// The temperatures padded to the given width, in the color of the theme or each along the gradient.
+fn paint_temperatures(temps: &[(f32, String)], params: &Params, width: usize) -> String {
+	let gui = &params.config.gui;
+	let plain = temps.iter().map(|(_, text)| text.as_str()).collect::<String>();
+	let padding = " ".repeat(width.saturating_sub(display_width(&plain)));
+
+	if gui.gradient {
+		let painted = temps
+			.iter()
+			.map(|(temp, text)| {
+				let celsius = params.config.units.temperature.to_celsius(*temp);
+				gui.gradient(celsius).paint(text).bold().to_string()
+			})
+			.collect::<String>();
+		painted + &padding
+	} else {
+		format!("{}", (plain + &padding).as_str().temperature_color(gui).bold())
+	}
+}
+
+fn prepare_precipitation(precipitation: &[u8], frame: &Frame) -> String {
 	let mut result = String::new();
 
-	for hour in DISPLAY_HOURS {
-		let precipitation_sup = style_number(precipitation[hour].into(), true);
-		let colspan = if hour == 0 { 2 } else { 8 };
+	for offset in frame.labelled_hours() {
+		let precipitation_sup = style_number(precipitation[offset].into(), true);
+		let colspan = if offset == 0 { 2 } else { frame.slot_width() - 1 };
 		let _ = write!(result, "{precipitation_sup: >colspan$} ");
 	}
 
 	result
 }
 
-#[allow(clippy::too_many_lines)]
-pub fn prep_historical(weather: &OptionalWeather, params: &Params) -> Result<Vec<String>> {
-	// If it's the last possible requested day, the last index(start_index of the 7th day) is not available.
-	// Therefore we'll extend the values by 1. For this we simply use the last value of the array twice.
-	let (mut temps, mut codes, mut prec);
-	let temperatures = {
-		temps = weather.hourly.temperature_2m.as_ref().unwrap()[0..].to_vec();
-		temps.push(temps[temps.len() - 1]);
-		&temps
-	};
-	let weather_codes = {
-		codes = weather.hourly.weathercode.as_ref().unwrap()[0..].to_vec();
-		codes.push(codes[codes.len() - 1]);
-		&codes
-	};
-	let sunrise_sunset = (
-		weather.daily.sunrise.as_ref().unwrap()[0][11..13]
-			.parse::<usize>()
-			.unwrap_or_default(),
-		weather.daily.sunset.as_ref().unwrap()[0][11..13]
-			.parse::<usize>()
-			.unwrap_or_default(),
-	);
-	let precipitation = {
-		prec = weather.hourly.precipitation.as_ref().unwrap()[0..].to_vec();
-		prec.push(prec[prec.len() - 1]);
-		prec.iter().map(|x| x.ceil() as u8).collect::<Vec<u8>>()
-	};
-
-	let (units, gui) = (&params.config.units, &params.config.gui);
-	let width_no_border_pad = WIDTH - 2;
-
-	let mut result = Vec::<String>::new();
-
-	// Blank Line
-	result.push(format!(
-		"{}",
-		&Separator::Blank.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color),
-	));
-
-	// Set Measurement Unit Symbols
-	let temperature_unit = match units.temperature {
-		Temperature::fahrenheit => "",
-		Temperature::celsius => "",
-	};
-	let precipitation_unit = match units.precipitation {
-		// In case the `probability` unit is configured while making a historical request,
-		// “mm” is set for precipitation (see weather.rs).
-		Precipitation::probability | Precipitation::mm => "ₘₘ",
-		Precipitation::inch => "ᵢₙ",
-	};
-
-	// Hourly Forecast Heading
-	result.push(format!(
-		"{} {} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		style(pad_string_to_width(&params.texts.weather.daily_overview, width_no_border_pad)).bold(),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-	));
+#[allow(clippy::cast_possible_wrap)]
+fn prepare_hours(time: Time, frame: &Frame) -> String {
+	let mut result = String::new();
+	let slot_width = frame.slot_width();
+
+	for offset in frame.labelled_hours() {
+		let hour = frame.hour_of_day(offset);
+		let (label, short_label) = match time {
+			Time::am_pm => {
+				let (hour, suffix) = (
+					style_number(if hour % 12 == 0 { 12 } else { (hour % 12) as i32 }, false),
+					if hour < 12 { "ₐₘ" } else { "ₚₘ" },
+				);
+				(format!("{hour}˙⁰⁰{suffix}"), format!("{hour}{suffix}"))
+			}
+			Time::military => {
+				let hour = format!("{}{}", if hour < 10 { "⁰" } else { "" }, style_number(hour as i32, false));
+				(format!("{hour}˙⁰⁰"), hour)
+			}
+		};
+		// Narrow slots only show the hour.
+		let label = if display_width(&label) < slot_width { label } else { short_label };
+		let _ = write!(result, "{label: <slot_width$}");
+	}
 
-	// Graph Border Top
-	result.push(format!(
-		"{}",
-		Separator::Dashed.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-	));
+	result
+}
 
-	// Temperatures
-	let temps = prepare_temperatures(temperatures, weather_codes, sunrise_sunset, &params.texts.weather.weather_code)?;
-	result.push(format!(
-		"{} {: <WIDTH$}{} {}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		temps.plain_or_yellow(gui.color).bold(),
-		temperature_unit.plain_or_yellow(gui.color).bold(),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-		WIDTH = WIDTH - 3
-	));
+pub fn prep_historical(weather: &OptionalWeather, params: &Params) -> Result<Vec<String>> {
+	// The hourly values of a historical date cover just that day.
+	let layout = params.layout;
+	let times = weather.hourly.time.as_deref().unwrap_or_default();
+	let day = hours_of_day(times, &weather.daily.time.as_ref().unwrap()[0]);
+	let frame = Frame::new(layout, params.config.gui.hourly.label_step, times, day.start, day.len());
+
+	let temperatures = frame.slice(weather.hourly.temperature_2m.as_ref().unwrap());
+	let weather_codes = frame.slice(weather.hourly.weathercode.as_ref().unwrap());
+	// Series without historical data are left out.
+	let series = params
+		.config
+		.gui
+		.graph
+		.series
+		.iter()
+		.filter_map(|&series| {
+			let hourly = &weather.hourly;
+			let values = match series {
+				Series::temperature => &hourly.temperature_2m,
+				Series::precipitation => &hourly.precipitation,
+				Series::wind => &hourly.windspeed_10m,
+				Series::humidity => &hourly.relativehumidity_2m,
+				Series::pressure => &hourly.surface_pressure,
+				Series::uv_index => &hourly.uv_index,
+			};
+			Some((series, frame.slice(values.as_ref()?)))
+		})
+		.collect::<Vec<_>>();
+	let daylight = frame.slice(&times.iter().map(|time| weather.daylight(time)).collect::<Vec<_>>());
+	let precipitation = frame
+		.slice(weather.hourly.precipitation.as_ref().unwrap())
+		.iter()
+		.map(|x| x.ceil() as u8)
+		.collect::<Vec<u8>>();
+
+	render(
+		&HourlyView {
+			heading: params.texts.weather.daily_overview.clone(),
+			summary: None,
+			temperatures,
+			weather_codes,
+			daylight,
+			series,
+			precipitation,
+			// Historical data has no probabilities, so it is requested in millimeters instead.
+			precipitation_unit: match params.config.units.precipitation {
+				Precipitation::probability => Precipitation::mm,
+				unit => unit,
+			},
+			time_indicator_col: None,
+		},
+		&frame,
+		params,
+	)
+}
 
-	// Blank Line
-	result.push(format!(
-		"{}",
-		&Separator::Blank.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-	));
+#[cfg(test)]
+mod tests {
+	use super::*;
 
-	let graph = Graph::prepare_graph(temperatures, &params.config.gui.graph);
-	// Graph Row 1
-	if graph.1.chars().count() > 0 {
-		result.push(format!(
-			"{}{}{}",
-			Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-			graph.1.plain_or_yellow(gui.color),
-			Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-		));
+	fn times(days: usize) -> Vec<String> {
+		(0..days * 24)
+			.map(|h| format!("2024-10-{:02}T{:02}:00", 7 + h / 24, h % 24))
+			.collect()
 	}
-	// Graph Row 2
-	result.push(format!(
-		"{}{}{}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		graph.0.plain_or_yellow(gui.color),
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-	));
 
-	// Precipitation
-	let precipitation = prepare_precipitation(&precipitation);
-	result.push(format!(
-		"{} {: <WIDTH$}{}{}",
-		Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
-		precipitation.plain_or_blue(gui.color).bold(),
-		if units.precipitation == Precipitation::probability {
-			// to enlarge the water percent icon we use bold as a hack
-			precipitation_unit.plain_or_blue(gui.color).bold()
-		} else {
-			precipitation_unit.plain_or_blue(gui.color)
-		},
-		Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
-		WIDTH = WIDTH - 1 - precipitation_unit.chars().count()
-	));
+	#[test]
+	fn layout_fits_terminal_width() {
+		assert_eq!(Layout::fit(60), Layout::Condensed);
+		assert_eq!(Layout::fit(74), Layout::Regular);
+		assert_eq!(Layout::fit(193), Layout::Regular);
+		assert_eq!(Layout::fit(200), Layout::Wide);
+		assert_eq!(Layout::detect(Some(80)), Layout::Regular);
+	}
 
-	// Graph Border Bottom
-	result.push(format!(
-		"{}",
-		Separator::Dashed.fmt(WIDTH, gui.border).plain_or_bright_black(gui.color)
-	));
+	#[test]
+	fn hour_labels() {
+		let frame = Frame::new(Layout::Condensed, LabelStep::auto, &times(2), 0, 24);
+		assert_eq!((frame.cols_per_hour, frame.label_step), (2, 6));
+		assert_eq!(
+			prepare_hours(Time::am_pm, &frame),
+			"¹²˙⁰⁰ₐₘ     ⁶˙⁰⁰ₐₘ      ¹²˙⁰⁰ₚₘ     ⁶˙⁰⁰ₚₘ      "
+		);
+
+		let frame = Frame::new(Layout::Wide, LabelStep::auto, &times(2), 0, 24);
+		assert!(prepare_hours(Time::military, &frame).starts_with("⁰⁰˙⁰⁰   ⁰¹˙⁰⁰   ⁰²˙⁰⁰   "));
+		assert!(prepare_hours(Time::military, &frame).ends_with("²²˙⁰⁰   ²³˙⁰⁰   "));
+
+		// Narrow slots only show the hour.
+		let frame = Frame::new(Layout::Regular, LabelStep::hours(1), &times(2), 0, 24);
+		assert!(prepare_hours(Time::military, &frame).starts_with("⁰⁰ ⁰¹ ⁰² "));
+	}
 
-	// Graph Hours Row
-	let mut hours_row = format!("{}", Border::L.fmt(gui.border).plain_or_bright_black(gui.color));
-	let hours = match units.time {
-		Time::am_pm => ["¹²˙⁰⁰ₐₘ", "³˙⁰⁰ₐₘ", "⁶˙⁰⁰ₐₘ", "⁹˙⁰⁰ₐₘ", "¹²˙⁰⁰ₚₘ", "³˙⁰⁰ₚₘ", "⁶˙⁰⁰ₚₘ", "⁹˙⁰⁰ₚₘ"],
-		Time::military => ["⁰⁰˙⁰⁰", "⁰³˙⁰⁰", "⁰⁶˙⁰⁰", "⁰⁹˙⁰⁰", "¹²˙⁰⁰", "¹⁵˙⁰⁰", "¹⁸˙⁰⁰", "²¹˙⁰⁰"],
-	};
-	for hour in hours {
-		hours_row.push_str(&format!("{hour: <9}"));
+	#[test]
+	fn window_across_midnight() {
+		let frame = Frame::new(Layout::Regular, LabelStep::auto, &times(2), 19, 12);
+		assert_eq!((frame.cols_per_hour, frame.label_step), (6, 2));
+		assert_eq!(
+			prepare_hours(Time::military, &frame),
+			"¹⁹˙⁰⁰       ²¹˙⁰⁰       ²³˙⁰⁰       ⁰¹˙⁰⁰       ⁰³˙⁰⁰       ⁰⁵˙⁰⁰       "
+		);
+		assert_eq!(
+			frame
+				.slice(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20])
+				.len(),
+			13
+		);
+		assert_eq!(frame.slice(&[0; 24])[12], 0);
+
+		// Windows are limited to two columns per hour.
+		assert_eq!(Frame::new(Layout::Condensed, LabelStep::auto, &times(2), 0, 48).hours, 24);
 	}
-	hours_row.push_str(&format!("{}", Border::R.fmt(gui.border).plain_or_bright_black(gui.color)));
-	result.push(hours_row);
 
-	Ok(result)
+	#[test]
+	fn time_indicator_of_a_single_hour() {
+		for layout in [Layout::Condensed, Layout::Regular, Layout::Wide] {
+			let (frame, width) = (Frame::new(layout, LabelStep::auto, &times(2), 5, 1), layout.width());
+			let col = frame.time_indicator_col(5, 59, width);
+			assert_eq!(col, width - 1, "{layout:?}");
+			let separator = prepare_separator(col, BorderStyle::rounded, width, '╤');
+			assert_eq!(display_width(&separator), width + 2, "{layout:?}");
+		}
+	}
 }
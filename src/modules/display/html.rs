@@ -0,0 +1,212 @@
+use anyhow::Result;
+
+use crate::modules::params::Params;
+
+use super::{
+	border::BorderStyle,
+	gui_config::{Gui, ThemeColor},
+	product::Product,
+	report::{Hours, Overview, Report, Section, WeekRow},
+};
+
+// A self-contained html document. Unless requested, Nerd Font icons are left out, as they can't be expected
+// to be available wherever the document is opened. The configured border style and color are used as theme.
+pub fn prep(product: &Product, params: &Params) -> Result<Vec<String>> {
+	let mut result = vec![
+		"<!DOCTYPE html>".to_string(),
+		format!("<html lang=\"{}\">", escape(&params.config.language.replace('_', "-"))),
+		"<head>".to_string(),
+		"<meta charset=\"utf-8\">".to_string(),
+		format!("<title>{}</title>", escape(&product.address)),
+		format!("<style>{}</style>", stylesheet(&params.config.gui)),
+		"</head>".to_string(),
+		"<body>".to_string(),
+	];
+
+	for section in Report::new(product, params)?.sections {
+		match section {
+			Section::Overview(overview) => result.append(&mut prep_overview(&product.address, &overview)),
+			Section::Week(rows) => result.append(&mut prep_week(&rows, params)),
+		}
+	}
+
+	result.push(format!(
+		"<footer>{}</footer>",
+		escape(&params.texts.disclaimer)
+			.replace("Open-Meteo.com", "<a href=\"https://open-meteo.com\">Open-Meteo.com</a>")
+	));
+	result.push("</body>".to_string());
+	result.push("</html>".to_string());
+
+	Ok(result)
+}
+
+fn prep_overview(address: &str, overview: &Overview) -> Vec<String> {
+	let mut result = vec![
+		"<section class=\"box\">".to_string(),
+		match &overview.date {
+			Some(date) => format!("<h1>{} <small>{}</small></h1>", escape(address), escape(date)),
+			None => format!("<h1>{}</h1>", escape(address)),
+		},
+		format!(
+			"<p class=\"headline\">{}, <span class=\"temp\">{}</span>{}</p>",
+			escape(&overview.weather),
+			escape(&overview.temperature),
+			overview
+				.precipitation
+				.as_ref()
+				.map(|precipitation| format!(" <span class=\"prec\">{}</span>", escape(precipitation)))
+				.unwrap_or_default()
+		),
+		format!("<p>{}</p>", escape(&overview.feels_like)),
+	];
+
+	if overview.details.is_empty() {
+		result.push(format!("<p>{} {}</p>", escape(&overview.sunrise), escape(&overview.sunset)));
+	} else {
+		result.push("<table class=\"details\">".to_string());
+		for pair in overview.details.chunks(2) {
+			result.push(match pair {
+				[single] => format!("<tr><td colspan=\"2\">{}</td></tr>", escape(single)),
+				_ => format!("<tr><td>{}</td><td>{}</td></tr>", escape(&pair[0]), escape(&pair[1])),
+			});
+		}
+		result.push(format!(
+			"<tr><td>{}</td><td>{}</td></tr>",
+			escape(&overview.sunrise),
+			escape(&overview.sunset)
+		));
+		result.push("</table>".to_string());
+	}
+
+	if let Some(hours) = &overview.hours {
+		result.append(&mut prep_hours(hours));
+	}
+	result.push("</section>".to_string());
+
+	result
+}
+
+fn prep_hours(hours: &Hours) -> Vec<String> {
+	let mut result = vec![
+		format!("<h2>{}</h2>", escape(&hours.heading)),
+		"<table class=\"hourly\">".to_string(),
+	];
+	for row in &hours.rows {
+		result.push(format!(
+			"<tr><td>{}</td><td>{}</td><td class=\"temp\">{}{}</td><td class=\"prec\">{}{}</td></tr>",
+			escape(&row.time),
+			escape(&row.weather),
+			row.temperature,
+			hours.temperature_unit,
+			row.precipitation,
+			hours.precipitation_unit,
+		));
+	}
+	result.push("</table>".to_string());
+
+	result
+}
+
+fn prep_week(rows: &[WeekRow], params: &Params) -> Vec<String> {
+	let temperature_unit = params.temperature_unit();
+
+	let mut result = vec!["<section class=\"box\">".to_string(), "<table class=\"week\">".to_string()];
+	for row in rows {
+		result.push(format!(
+			"<tr><td>{}</td><td class=\"temp\">{}{temperature_unit}/{}{temperature_unit}</td><td class=\"prec\">{}%</td><td>{}</td></tr>",
+			escape(&row.date),
+			row.temperature_max,
+			row.temperature_min,
+			row.precipitation,
+			escape(&row.weather),
+		));
+	}
+	result.push("</table>".to_string());
+	result.push("</section>".to_string());
+
+	result
+}
+
+fn stylesheet(gui: &Gui) -> String {
+	let (border_width, border_line, border_radius) = match gui.border {
+		BorderStyle::rounded => (1, "solid", 8),
+		BorderStyle::single => (1, "solid", 0),
+		BorderStyle::solid => (2, "solid", 0),
+		BorderStyle::double => (3, "double", 0),
+	};
+	let palette = gui.palette();
+	let color = |color: ThemeColor, fallback: &str| color.hex().unwrap_or_else(|| fallback.to_string());
+	let (border_color, temperature_color, precipitation_color, heading_color) = (
+		color(palette.border, "currentColor"),
+		color(palette.temperature, "inherit"),
+		color(palette.precipitation, "inherit"),
+		color(palette.heading, "inherit"),
+	);
+
+	format!(
+		"body{{font-family:sans-serif;max-width:40em;margin:1em auto;padding:0 1em}}\
+.box{{border:{border_width}px {border_line} {border_color};border-radius:{border_radius}px;padding:0 1em 1em;margin-bottom:1em}}\
+h1{{font-size:1.2em;border-bottom:1px solid {border_color};padding-bottom:.5em;color:{heading_color}}}\
+h2{{font-size:1em;color:{heading_color}}}\
+.headline{{font-weight:bold}}\
+table{{border-collapse:collapse;width:100%}}\
+td{{padding:.2em .5em}}\
+.hourly tr+tr td,.week tr+tr td{{border-top:1px dashed {border_color}}}\
+.temp{{color:{temperature_color}}}\
+.prec{{color:{precipitation_color}}}\
+footer{{color:{border_color};font-size:.8em}}"
+	)
+}
+
+fn escape(s: &str) -> String {
+	s.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::{
+		args::Forecast,
+		display::gui_config::ColorVariant,
+		display::utils::common_tests::{init_test_params, init_test_product, TEST_PRODUCT},
+	};
+	use std::collections::HashSet;
+
+	#[test]
+	fn output_with_day_and_week() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.config.forecast = HashSet::from([Forecast::day, Forecast::week]);
+
+		let html = prep(test_product, &test_params).unwrap().join("\n");
+
+		assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en-US\">"));
+		assert!(html.contains("<h1>Berlin, Germany</h1>"));
+		assert!(html.contains("<p class=\"headline\">Overcast, <span class=\"temp\">14.0°C</span></p>"));
+		assert!(html.contains(
+			"<tr><td>13:00</td><td>Overcast</td><td class=\"temp\">13.7°C</td><td class=\"prec\">8%</td></tr>"
+		));
+		assert!(html.contains(
+			"<tr><td>Sun, 13 Oct</td><td class=\"temp\">10.9°C/5.6°C</td><td class=\"prec\">32%</td><td>Slight Rain Showers</td></tr>"
+		));
+		assert!(html.ends_with("</body>\n</html>"));
+	}
+
+	#[test]
+	fn theme_from_gui_config() {
+		let gui = Gui {
+			border: BorderStyle::double,
+			color: ColorVariant::plain,
+			..Default::default()
+		};
+
+		let css = stylesheet(&gui);
+
+		assert!(css.contains(".box{border:3px double currentColor;border-radius:0px;"));
+		assert!(css.contains(".temp{color:inherit}"));
+	}
+}
@@ -0,0 +1,306 @@
+use serde::{Deserialize, Serialize};
+
+use crate::modules::{moon::Phase, sun::Daylight, units::Temperature};
+
+use super::wind::WindDirection;
+
+// Sets of glyphs, from Nerd Font icons to emoji, symbols that most fonts cover and plain ASCII for terminals
+// without either.
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum IconSet {
+	#[default]
+	nerd,
+	emoji,
+	unicode,
+	ascii,
+}
+
+// Glyphs besides the ones of the weather and the moon phases.
+#[derive(Clone, Copy)]
+pub enum Icon {
+	Sunrise,
+	Sunset,
+	Moonrise,
+	Moonset,
+	Pressure,
+	// Precipitation amount and probability
+	Precipitation,
+	Probability,
+	Calendar,
+	Celsius,
+	Fahrenheit,
+	Kelvin,
+	// Units of precipitation amounts
+	Millimeters,
+	Inches,
+	// Around the summary of a day, e.g., the maximum precipitation probability
+	SummaryStart,
+	SummaryEnd,
+}
+
+impl IconSet {
+	pub const fn get(self, icon: Icon) -> &'static str {
+		match self {
+			Self::nerd => match icon {
+				Icon::Sunrise => "\u{e34c}",
+				Icon::Sunset => "\u{e343}",
+				Icon::Moonrise => "\u{e3c1}",
+				Icon::Moonset => "\u{e3c2}",
+				Icon::Pressure => "\u{e3a9}",
+				Icon::Precipitation => "\u{e34a}",
+				Icon::Probability => "\u{f058e}",
+				Icon::Calendar => "\u{eab0}",
+				Icon::Celsius => "\u{e339}",
+				Icon::Fahrenheit => "\u{e341}",
+				Icon::Kelvin => "K",
+				Icon::Millimeters => "ₘₘ",
+				Icon::Inches => "ᵢₙ",
+				Icon::SummaryStart => "❲",
+				Icon::SummaryEnd => "❳",
+			},
+			Self::emoji => match icon {
+				Icon::Sunrise => "🌅",
+				Icon::Sunset => "🌇",
+				Icon::Moonrise => "🌙↑",
+				Icon::Moonset => "🌙↓",
+				Icon::Pressure => "⏲\u{fe0f}",
+				Icon::Precipitation => "☔",
+				Icon::Probability => "💧",
+				Icon::Calendar => "📅",
+				Icon::Celsius => "℃",
+				Icon::Fahrenheit => "℉",
+				Icon::Kelvin => "K",
+				Icon::Millimeters => "ₘₘ",
+				Icon::Inches => "ᵢₙ",
+				Icon::SummaryStart => "❲",
+				Icon::SummaryEnd => "❳",
+			},
+			Self::unicode => match icon {
+				Icon::Sunrise => "☀↑",
+				Icon::Sunset => "☀↓",
+				Icon::Moonrise => "☾↑",
+				Icon::Moonset => "☾↓",
+				Icon::Pressure => "⏲",
+				Icon::Precipitation => "☂",
+				Icon::Probability => "%",
+				Icon::Calendar => "▦",
+				Icon::Celsius => "℃",
+				Icon::Fahrenheit => "℉",
+				Icon::Kelvin => "K",
+				Icon::Millimeters => "ₘₘ",
+				Icon::Inches => "ᵢₙ",
+				Icon::SummaryStart => "❲",
+				Icon::SummaryEnd => "❳",
+			},
+			Self::ascii => match icon {
+				Icon::Sunrise => "^",
+				Icon::Sunset => "v",
+				Icon::Moonrise => "(^",
+				Icon::Moonset => "(v",
+				Icon::Pressure => "P",
+				Icon::Precipitation | Icon::Probability => "%",
+				Icon::Calendar => "#",
+				Icon::Celsius => "C",
+				Icon::Fahrenheit => "F",
+				Icon::Kelvin => "K",
+				Icon::Millimeters => "mm",
+				Icon::Inches => "in",
+				Icon::SummaryStart => "[",
+				Icon::SummaryEnd => "]",
+			},
+		}
+	}
+
+	pub const fn temperature(self, unit: Temperature) -> &'static str {
+		self.get(match unit {
+			Temperature::celsius => Icon::Celsius,
+			Temperature::fahrenheit => Icon::Fahrenheit,
+			Temperature::kelvin => Icon::Kelvin,
+		})
+	}
+
+	pub fn weather(self, code: u8, daylight: Daylight) -> &'static str {
+		// A clear sky during civil twilight shows the sun at the horizon where the set has a glyph for it.
+		if let (0 | 1, Daylight::CivilTwilight { rising }, Self::nerd | Self::emoji) = (code, daylight, self) {
+			return self.get(if rising { Icon::Sunrise } else { Icon::Sunset });
+		}
+
+		let (night, day) = match self {
+			Self::nerd => nerd(code),
+			Self::emoji => emoji(code),
+			Self::unicode => unicode(code),
+			Self::ascii => ascii(code),
+		};
+		if daylight.is_night() {
+			night
+		} else {
+			day
+		}
+	}
+
+	pub const fn moon(self, phase: Phase) -> &'static str {
+		let icons = match self {
+			Self::nerd => [
+				"\u{e38d}", "\u{e390}", "\u{e394}", "\u{e398}", "\u{e39b}", "\u{e39f}", "\u{e3a2}", "\u{e3a6}",
+			],
+			Self::emoji => ["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"],
+			Self::unicode => ["○", "☽", "◑", "◑", "●", "◐", "◐", "☾"],
+			Self::ascii => ["o", ")", "D", "D", "O", "C", "C", "("],
+		};
+		icons[phase as usize]
+	}
+
+	// Arrows in the direction the wind blows to.
+	pub const fn wind(self, direction: WindDirection) -> &'static str {
+		let icons = match self {
+			Self::nerd | Self::emoji | Self::unicode => ["↘", "↓", "↙", "←", "↖", "↑", "↗", "→"],
+			Self::ascii => ["\\", "v", "/", "<", "\\", "^", "/", ">"],
+		};
+		icons[direction as usize]
+	}
+}
+
+// Icons of the weather codes by night and by day.
+fn nerd(code: u8) -> (&'static str, &'static str) {
+	match code {
+		0 => ("", ""),
+		1 => ("", ""),
+		2 => ("", ""),
+		3 => ("", ""),
+		45 => ("", ""),
+		48 => ("", ""),
+		51 => ("", ""),
+		53 => ("", ""),
+		55 => ("", ""),
+		56 => ("", "󰼵"),
+		57 => ("", "󰙿"),
+		61 | 80 => ("", ""),
+		63 => ("", ""),
+		65 => ("", ""),
+		66 => ("", ""),
+		67 => ("", ""),
+		71 | 73 | 85 | 86 => ("", ""),
+		75 => ("", ""),
+		77 => ("", ""),
+		81 => ("", ""),
+		82 => ("", ""),
+		95 => ("", ""),
+		96 => ("", ""),
+		99 => ("", ""),
+		_ => ("?", "?"),
+	}
+}
+
+fn emoji(code: u8) -> (&'static str, &'static str) {
+	match code {
+		0 => ("🌙", "☀️"),
+		1 => ("🌙", "🌤️"),
+		2 => ("☁️", "⛅"),
+		3 => ("☁️", "☁️"),
+		45 | 48 => ("🌫️", "🌫️"),
+		51 | 53 | 61 | 80 | 81 => ("🌧️", "🌦️"),
+		55 | 63 | 65 | 82 => ("🌧️", "🌧️"),
+		56 | 57 | 66 | 67 => ("🧊", "🧊"),
+		71 | 73 | 75 | 85 | 86 => ("🌨️", "🌨️"),
+		77 => ("❄️", "❄️"),
+		95 | 96 | 99 => ("⛈️", "⛈️"),
+		_ => ("?", "?"),
+	}
+}
+
+fn unicode(code: u8) -> (&'static str, &'static str) {
+	match code {
+		0 => ("☾", "☀"),
+		1 => ("☾", "☼"),
+		2 | 3 => ("☁", "☁"),
+		45 | 48 => ("≡", "≡"),
+		51 | 53 | 55 | 61 | 63 | 65 | 80..=82 => ("☂", "☂"),
+		56 | 57 | 66 | 67 => ("❆", "❆"),
+		71 | 73 | 75 | 85 | 86 => ("❄", "❄"),
+		77 => ("❅", "❅"),
+		95 | 96 | 99 => ("ϟ", "ϟ"),
+		_ => ("?", "?"),
+	}
+}
+
+fn ascii(code: u8) -> (&'static str, &'static str) {
+	match code {
+		0 => (")", "O"),
+		1 => (")", "o"),
+		2 => ("%", "%"),
+		3 => ("#", "#"),
+		45 | 48 => ("=", "="),
+		51 | 53 | 55 => (",", ","),
+		56 | 57 | 66 | 67 => (";", ";"),
+		61 | 63 | 65 | 80..=82 => ("/", "/"),
+		71 | 73 | 75 | 85 | 86 => ("*", "*"),
+		77 => (".", "."),
+		95 | 96 | 99 => ("!", "!"),
+		_ => ("?", "?"),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::display::utils::display_width;
+
+	const CODES: [u8; 28] = [
+		0, 1, 2, 3, 45, 48, 51, 53, 55, 56, 57, 61, 63, 65, 66, 67, 71, 73, 75, 77, 80, 81, 82, 85, 86, 95, 96, 99,
+	];
+
+	#[test]
+	fn sets_cover_all_weather_codes() {
+		for set in [IconSet::nerd, IconSet::emoji, IconSet::unicode, IconSet::ascii] {
+			for code in CODES {
+				for daylight in [Daylight::Day, Daylight::Night] {
+					let icon = set.weather(code, daylight);
+					assert_ne!(icon, "?", "{set:?} {code}");
+					// Emoji take two columns, the others one.
+					assert_eq!(display_width(icon), if set == IconSet::emoji { 2 } else { 1 }, "{set:?} {code}");
+				}
+			}
+		}
+		assert!(IconSet::ascii.weather(0, Daylight::Day).is_ascii());
+	}
+
+	#[test]
+	fn ascii_set_is_ascii() {
+		let icons = [
+			Icon::Sunrise,
+			Icon::Sunset,
+			Icon::Moonrise,
+			Icon::Moonset,
+			Icon::Pressure,
+			Icon::Precipitation,
+			Icon::Probability,
+			Icon::Calendar,
+			Icon::Celsius,
+			Icon::Fahrenheit,
+			Icon::Kelvin,
+			Icon::Millimeters,
+			Icon::Inches,
+			Icon::SummaryStart,
+			Icon::SummaryEnd,
+		];
+		for icon in icons {
+			let glyph = IconSet::ascii.get(icon);
+			assert!(!glyph.is_empty() && glyph.is_ascii(), "{glyph:?}");
+		}
+
+		let directions = [0.0, 45.0, 90.0, 135.0, 180.0, 225.0, 270.0, 315.0]
+			.map(|degrees| IconSet::ascii.wind(WindDirection::get_direction(degrees).unwrap()));
+		assert_eq!(directions, ["v", "/", "<", "\\", "^", "/", ">", "\\"]);
+		assert_eq!(IconSet::nerd.wind(WindDirection::N), "↓");
+	}
+
+	#[test]
+	fn twilight() {
+		let dawn = Daylight::CivilTwilight { rising: true };
+		assert_eq!(IconSet::nerd.weather(0, dawn), IconSet::nerd.get(Icon::Sunrise));
+		assert_eq!(IconSet::emoji.weather(1, dawn), "🌅");
+		assert_eq!(IconSet::unicode.weather(0, dawn), "☀");
+		assert_eq!(IconSet::ascii.weather(3, dawn), "#");
+	}
+}
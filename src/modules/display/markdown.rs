@@ -0,0 +1,188 @@
+use anyhow::Result;
+
+use crate::modules::params::Params;
+
+use super::{
+	hourly::LabelStep,
+	product::Product,
+	report::{Hours, Overview, Report, Section, WeekRow},
+};
+
+// GitHub flavored markdown, e.g., to paste into notes or chats where box-drawing characters get mangled.
+pub fn prep(product: &Product, params: &Params) -> Result<Vec<String>> {
+	let mut result = vec![format!("# {}", escape(&product.address))];
+
+	for section in Report::new(product, params)?.sections {
+		match section {
+			Section::Overview(overview) => result.append(&mut prep_overview(&overview, params)),
+			Section::Week(rows) => result.append(&mut prep_week(&rows, params)),
+		}
+	}
+
+	result.push(String::new());
+	result.push(format!("_{}_", params.texts.disclaimer));
+
+	Ok(result)
+}
+
+fn prep_overview(overview: &Overview, params: &Params) -> Vec<String> {
+	let mut result = vec![String::new()];
+	if let Some(date) = &overview.date {
+		result.push(format!("## {}", date.replace("  ", " ")));
+		result.push(String::new());
+	}
+	result.push(match &overview.precipitation {
+		Some(precipitation) => format!("**{}, {}** ({precipitation})  ", overview.weather, overview.temperature),
+		None => format!("**{}, {}**  ", overview.weather, overview.temperature),
+	});
+
+	if overview.details.is_empty() {
+		result.push(format!("{}  ", overview.feels_like));
+		result.push(format!("{} {}", overview.sunrise, overview.sunset));
+	} else {
+		result.push(overview.feels_like.clone());
+		result.push(String::new());
+		result.extend(overview.details.iter().map(|detail| format!("- {detail}")));
+		result.push(format!("- {} {}", overview.sunrise, overview.sunset));
+	}
+
+	if let Some(hours) = &overview.hours {
+		result.append(&mut prep_hours(hours, params.config.gui.hourly.label_step));
+	}
+
+	result
+}
+
+// The hours are transposed into columns, with the units leading their rows. A set label step leaves out the
+// hours between two labels.
+fn prep_hours(hours: &Hours, label_step: LabelStep) -> Vec<String> {
+	let step = match label_step {
+		LabelStep::auto => 1,
+		LabelStep::hours(step) => usize::from(step),
+	};
+
+	let mut times = vec![String::new()];
+	let mut codes = vec![String::new()];
+	let mut temperatures = vec![hours.temperature_unit.clone()];
+	let mut precipitation = vec![hours.precipitation_unit.clone()];
+
+	for row in hours.rows.iter().filter(|row| row.hour % step == 0) {
+		times.push(row.time.clone());
+		codes.push(row.weather.clone());
+		temperatures.push(row.temperature.clone());
+		precipitation.push(row.precipitation.clone());
+	}
+
+	vec![
+		String::new(),
+		format!("### {}", hours.heading),
+		String::new(),
+		table_row(&times),
+		table_row(&vec!["---".to_string(); times.len()]),
+		table_row(&codes),
+		table_row(&temperatures),
+		table_row(&precipitation),
+	]
+}
+
+fn prep_week(rows: &[WeekRow], params: &Params) -> Vec<String> {
+	let mut result = vec![
+		String::new(),
+		table_row(&[
+			String::new(),
+			String::new(),
+			params.temperature_unit().to_string(),
+			"%".to_string(),
+		]),
+		table_row(&vec!["---".to_string(); 4]),
+	];
+	result.extend(rows.iter().map(|row| {
+		table_row(&[
+			row.date.replace("  ", " "),
+			row.weather.clone(),
+			format!("{}/{}", row.temperature_max, row.temperature_min),
+			row.precipitation.clone(),
+		])
+	}));
+
+	result
+}
+
+fn table_row(cells: &[String]) -> String {
+	format!("| {} |", cells.iter().map(|cell| escape(cell)).collect::<Vec<_>>().join(" | "))
+}
+
+fn escape(s: &str) -> String {
+	s.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::{
+		args::Forecast,
+		display::utils::common_tests::{init_test_params, init_test_product, TEST_PRODUCT},
+	};
+	use std::collections::HashSet;
+
+	#[test]
+	fn output() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.config.forecast = HashSet::from([Forecast::day, Forecast::week]);
+
+		let want = "\
+# Berlin, Germany
+
+**Overcast, 14.0°C**  
+Feels like 11.8°C
+
+- Humidity: 72%
+- Dew Point: 8.7°C
+- ↑ 10.7km/h S
+- 1001.3hPa
+- Visibility: 24.1km
+- ☀↑ 07:18 ☀↓ 18:29
+
+### Hourly Forecast
+
+|  | 00:00 | 01:00 | 02:00 | 03:00 | 04:00 | 05:00 | 06:00 | 07:00 | 08:00 | 09:00 | 10:00 | 11:00 | 12:00 | 13:00 | 14:00 | 15:00 | 16:00 | 17:00 | 18:00 | 19:00 | 20:00 | 21:00 | 22:00 | 23:00 |
+| --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- |
+|  | Mostly Clear | Partly Cloudy | Partly Cloudy | Mostly Clear | Mostly Clear | Mostly Clear | Partly Cloudy | Partly Cloudy | Partly Cloudy | Partly Cloudy | Overcast | Overcast | Overcast | Overcast | Overcast | Overcast | Overcast | Slight Rain Showers | Overcast | Overcast | Overcast | Overcast | Overcast | Overcast |
+| °C | 8.6 | 8.2 | 8.0 | 7.8 | 7.5 | 7.2 | 7.0 | 6.8 | 6.8 | 7.3 | 8.7 | 10.6 | 12.4 | 13.7 | 14.7 | 15.1 | 15.0 | 14.6 | 14.4 | 14.1 | 13.9 | 13.6 | 13.4 | 13.1 |
+| % | 0 | 0 | 0 | 0 | 0 | 0 | 0 | 0 | 0 | 0 | 0 | 0 | 0 | 8 | 25 | 25 | 10 | 5 | 0 | 0 | 0 | 0 | 0 | 0 |
+
+|  |  | °C | % |
+| --- | --- | --- | --- |
+| Mon, 7 Oct | Slight Rain Showers | 15.1/6.8 | 25 |
+| Tue, 8 Oct | Slight Rain | 20.3/12.0 | 88 |
+| Wed, 9 Oct | Moderate Rain | 17.9/13.7 | 98 |
+| Thu, 10 Oct | Slight Rain | 20.2/13.7 | 50 |
+| Fri, 11 Oct | Overcast | 13.2/7.9 | 21 |
+| Sat, 12 Oct | Overcast | 12.2/4.8 | 11 |
+| Sun, 13 Oct | Slight Rain Showers | 10.9/5.6 | 32 |
+
+_Weather data by Open-Meteo.com_";
+
+		let have = prep(test_product, &test_params).unwrap().join("\n");
+		assert_eq!(want, have);
+	}
+
+	#[test]
+	fn hours_by_label_step() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.config.forecast = HashSet::from([Forecast::day]);
+		test_params.config.gui.hourly.label_step = LabelStep::hours(3);
+
+		let want = "\
+|  | 00:00 | 03:00 | 06:00 | 09:00 | 12:00 | 15:00 | 18:00 | 21:00 |
+| --- | --- | --- | --- | --- | --- | --- | --- | --- |
+|  | Mostly Clear | Mostly Clear | Partly Cloudy | Partly Cloudy | Overcast | Overcast | Overcast | Overcast |
+| °C | 8.6 | 7.8 | 7.0 | 7.3 | 12.4 | 15.1 | 14.4 | 13.6 |
+| % | 0 | 0 | 0 | 0 | 0 | 25 | 0 | 0 |";
+
+		let have = prep(test_product, &test_params).unwrap().join("\n");
+		assert!(have.contains(want));
+	}
+}
@@ -2,11 +2,17 @@ pub mod border;
 pub mod graph;
 pub mod gui_config;
 pub mod hourly;
+pub mod icons;
 pub mod product;
 
+mod astronomy;
 mod current;
 mod day;
+mod export;
 mod historical;
+mod html;
+mod markdown;
+mod report;
 mod utils;
 mod weathercode;
 mod week;
@@ -1,15 +1,16 @@
 use anyhow::Result;
-use chrono::{Datelike, NaiveDate};
+use chrono::NaiveDate;
 use scopeguard::defer;
 use std::collections::HashMap;
 
 use crate::modules::{
+	args::{Forecast, Output},
 	forecast,
 	params::Params,
 	weather::{OptionalWeather, Weather},
 };
 
-use super::{current, day, gui_config::ConfigurableColor, historical, week};
+use super::{current, day, export, gui_config::ConfigurableColor, historical, html, markdown, week};
 
 #[derive(Debug)]
 pub struct Product<'a> {
@@ -24,12 +25,21 @@ pub const TOTAL_BORDER_PADDING: usize = 2;
 
 impl Product<'_> {
 	pub fn render(&self, params: &Params) -> Result<()> {
+		if let Some(output) = params.output {
+			Self::print_lines(match output {
+				Output::html => html::prep(self, params)?,
+				Output::markdown => markdown::prep(self, params)?,
+				Output::csv | Output::tsv => export::prep(self, params, output)?,
+			});
+			return Ok(());
+		}
+
 		defer! {
 			// Disclaimer
-			println!(" {}", "Weather data by Open-Meteo.com\n".plain_or_bright_black(params.config.gui.color))
+			println!(" {}\n", params.texts.disclaimer.as_str().border_color(&params.config.gui))
 		}
 
-		if params.config.forecast.is_empty() && params.historical_weather.is_empty() {
+		if params.config.forecast.is_empty() && params.days.is_empty() && params.historical_weather.is_empty() {
 			// Current day without hours
 			let (lines, _) = current::prep(self, params, false)?;
 			Self::print_lines(lines);
@@ -40,30 +50,33 @@ impl Product<'_> {
 			Self::print_lines(historical::prep(self, params, *date)?);
 		}
 
-		if params.config.forecast.is_empty() {
+		if params.config.forecast.is_empty() && params.days.is_empty() {
 			return Ok(());
 		}
 
-		let current_date = NaiveDate::parse_from_str(&self.weather.current_weather.time, "%Y-%m-%dT%H:%M")?;
-		let forecast_indices = forecast::get_indices(&params.config.forecast, current_date.weekday());
+		let forecast_indices = forecast::get_indices(params, &self.weather)?;
+		let (today, week) = (
+			forecast_indices.contains(&self.weather.past_days),
+			params.config.forecast.contains(&Forecast::week),
+		);
 
-		if forecast_indices.contains(&0) && forecast_indices.contains(&7) {
+		if today && week {
 			// Current day with hours & weekly overview
 			let (lines, dimensions) = current::prep(self, params, true)?;
 			Self::print_lines(lines);
 			Self::print_lines(week::prep(self, params, Some(dimensions))?);
-		} else if forecast_indices.contains(&0) {
+		} else if today {
 			// Current day with hours
 			let (lines, _) = current::prep(self, params, true)?;
 			Self::print_lines(lines);
-		} else if forecast_indices.contains(&7) {
+		} else if week {
 			// Weekly overview only
 			Self::print_lines(week::prep(self, params, None)?);
-		};
+		}
 
 		for i in forecast_indices {
-			// Weekdays
-			if i < 7 && i > 0 {
+			// Other days
+			if i != self.weather.past_days {
 				Self::print_lines(day::prep(self, params, i)?);
 			}
 		}
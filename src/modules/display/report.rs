@@ -0,0 +1,401 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+
+use crate::modules::{
+	args::Forecast,
+	forecast,
+	params::Params,
+	sun::Daylight,
+	units::Precipitation,
+	weather::{hour_of, OptionalWeather},
+};
+
+use super::{
+	icons::Icon,
+	product::Product,
+	utils::{format_date, format_hour, format_sun_times},
+	weathercode::WeatherCode,
+	wind::WindDirection,
+};
+
+// The contents of the html and markdown outputs, which only differ in how they serialize them.
+pub struct Report {
+	pub sections: Vec<Section>,
+}
+
+pub enum Section {
+	Overview(Box<Overview>),
+	Week(Vec<WeekRow>),
+}
+
+// The current weather, a day of the forecast or a historical date.
+pub struct Overview {
+	// Left out for the current weather
+	pub date: Option<String>,
+	pub weather: String,
+	pub temperature: String,
+	pub precipitation: Option<String>,
+	pub feels_like: String,
+	// Humidity, dew point, wind, pressure and visibility of the current weather
+	pub details: Vec<String>,
+	pub sunrise: String,
+	pub sunset: String,
+	pub hours: Option<Hours>,
+}
+
+pub struct Hours {
+	pub heading: String,
+	pub temperature_unit: String,
+	pub precipitation_unit: String,
+	pub rows: Vec<HourRow>,
+}
+
+// Values of the rows are given without their units, which the outputs place differently.
+pub struct HourRow {
+	pub hour: usize,
+	pub time: String,
+	pub weather: String,
+	pub temperature: String,
+	pub precipitation: String,
+}
+
+pub struct WeekRow {
+	pub date: String,
+	pub weather: String,
+	pub temperature_max: String,
+	pub temperature_min: String,
+	pub precipitation: String,
+}
+
+impl Report {
+	pub fn new(product: &Product, params: &Params) -> Result<Self> {
+		let mut sections = vec![];
+
+		if params.config.forecast.is_empty() && params.days.is_empty() && params.historical_weather.is_empty() {
+			sections.push(Section::Overview(Box::new(current(product, params, false)?)));
+		}
+
+		let mut dates = params.historical_weather.iter().collect::<Vec<_>>();
+		dates.sort();
+		for date in dates {
+			sections.push(Section::Overview(Box::new(historical(product, params, *date)?)));
+		}
+
+		if !params.config.forecast.is_empty() || !params.days.is_empty() {
+			let forecast_indices = forecast::get_indices(params, &product.weather)?;
+			let today = product.weather.past_days;
+
+			if forecast_indices.contains(&today) {
+				sections.push(Section::Overview(Box::new(current(product, params, true)?)));
+			}
+			if params.config.forecast.contains(&Forecast::week) {
+				sections.push(Section::Week(week(product, params)?));
+			}
+			for i in forecast_indices {
+				if i != today {
+					sections.push(Section::Overview(Box::new(day(product, params, i)?)));
+				}
+			}
+		}
+
+		Ok(Self { sections })
+	}
+}
+
+// Nerd Font icons are only used if requested, as they can't be expected to be available wherever the output is
+// opened.
+fn sun_icons(params: &Params) -> (&str, &str) {
+	let icons = params.config.gui.icons;
+	if params.icons {
+		(icons.get(Icon::Sunrise), icons.get(Icon::Sunset))
+	} else {
+		("☀↑", "☀↓")
+	}
+}
+
+fn probability_icon(params: &Params) -> &str {
+	if params.icons {
+		params.config.gui.icons.get(Icon::Probability)
+	} else {
+		"%"
+	}
+}
+
+fn current(product: &Product, params: &Params, add_hourly: bool) -> Result<Overview> {
+	let (weather, t) = (&product.weather, &params.texts.weather);
+
+	let (today, hour_index) = (weather.past_days, weather.current_hour_index());
+	let wmo_code = WeatherCode::resolve(
+		weather.current_weather.weathercode,
+		weather.daylight(&weather.current_weather.time),
+		&t.weather_code,
+		&params.config.gui,
+	)?;
+	let (sunrise, sunset) = params.format_sun_times(weather, today)?;
+	let (sunrise_icon, sunset_icon) = sun_icons(params);
+	let wind_direction = WindDirection::get_direction(weather.current_weather.winddirection)?;
+	let pressure_icon = if params.icons {
+		format!("{} ", params.config.gui.icons.get(Icon::Pressure))
+	} else {
+		String::new()
+	};
+
+	Ok(Overview {
+		date: None,
+		weather: wmo_code.describe(params.icons),
+		temperature: format!(
+			"{}{}",
+			params.format_decimal(weather.current_weather.temperature),
+			params.temperature_unit()
+		),
+		precipitation: None,
+		feels_like: format!(
+			"{} {}{}",
+			t.feels_like,
+			params.format_decimal(weather.hourly.apparent_temperature[hour_index]),
+			params.temperature_unit()
+		),
+		details: vec![
+			format!(
+				"{}: {}{}",
+				t.humidity, weather.hourly.relativehumidity_2m[hour_index], weather.hourly_units.relativehumidity_2m
+			),
+			format!(
+				"{}: {}{}",
+				t.dew_point,
+				params.format_decimal(weather.hourly.dewpoint_2m[hour_index]),
+				params.temperature_unit()
+			),
+			format!(
+				"{} {}",
+				params.config.gui.icons.wind(wind_direction),
+				params.format_wind(weather.current_weather.windspeed, wind_direction.localize(&t.wind_direction))
+			),
+			format!(
+				"{pressure_icon}{}",
+				params.format_pressure(weather.hourly.surface_pressure[hour_index])
+			),
+			format!(
+				"{}: {}",
+				t.visibility,
+				params.format_visibility(weather.hourly.visibility[hour_index])
+			),
+		],
+		sunrise: format!("{sunrise_icon} {sunrise}"),
+		sunset: format!("{sunset_icon} {sunset}"),
+		hours: if add_hourly { Some(hours(product, params, today)?) } else { None },
+	})
+}
+
+fn day(product: &Product, params: &Params, day_index: usize) -> Result<Overview> {
+	let (weather, t) = (&product.weather, &params.texts.weather);
+
+	let dt = NaiveDate::parse_from_str(&weather.daily.time[day_index], "%Y-%m-%d")?;
+	let wmo_code = WeatherCode::resolve(
+		weather.daily.weathercode[day_index],
+		Daylight::Day,
+		&t.weather_code,
+		&params.config.gui,
+	)?;
+	let (sunrise, sunset) = params.format_sun_times(weather, day_index)?;
+	let (sunrise_icon, sunset_icon) = sun_icons(params);
+
+	Ok(Overview {
+		date: Some(format_date(dt, &params.config.language)?),
+		weather: wmo_code.describe(params.icons),
+		temperature: format!(
+			"{}/{}{}",
+			params.format_decimal(weather.daily.temperature_2m_max[day_index]),
+			params.format_decimal(weather.daily.temperature_2m_min[day_index]),
+			params.temperature_unit()
+		),
+		precipitation: Some(format!(
+			"{}{}",
+			weather.daily.precipitation_probability_max[day_index],
+			probability_icon(params)
+		)),
+		feels_like: format!(
+			"{} {}/{}{}",
+			t.feels_like,
+			params.format_decimal(weather.daily.apparent_temperature_max[day_index]),
+			params.format_decimal(weather.daily.apparent_temperature_min[day_index]),
+			params.temperature_unit()
+		),
+		details: vec![],
+		sunrise: format!("{sunrise_icon} {sunrise}"),
+		sunset: format!("{sunset_icon} {sunset}"),
+		hours: Some(hours(product, params, day_index)?),
+	})
+}
+
+fn hours(product: &Product, params: &Params, day_index: usize) -> Result<Hours> {
+	let weather = &product.weather;
+	let units = &params.config.units;
+
+	let rows = weather
+		.hours_of_day(day_index)
+		.map(|idx| {
+			let hour = hour_of(&weather.hourly.time[idx]);
+			let wmo_code = WeatherCode::resolve(
+				weather.hourly.weathercode[idx],
+				weather.daylight(&weather.hourly.time[idx]),
+				&params.texts.weather.weather_code,
+				&params.config.gui,
+			)?;
+			Ok(HourRow {
+				hour,
+				time: format_hour(hour, units.time),
+				weather: wmo_code.describe(params.icons),
+				temperature: params.format_decimal(weather.hourly.temperature_2m[idx]),
+				precipitation: match units.precipitation {
+					Precipitation::probability => weather.hourly.precipitation_probability[idx].to_string(),
+					_ => params.format_decimal(weather.hourly.precipitation[idx]),
+				},
+			})
+		})
+		.collect::<Result<_>>()?;
+
+	Ok(Hours {
+		heading: params.texts.weather.hourly_forecast.clone(),
+		temperature_unit: params.temperature_unit().to_string(),
+		precipitation_unit: match units.precipitation {
+			Precipitation::probability => "%".to_string(),
+			_ => params.precipitation_unit().to_string(),
+		},
+		rows,
+	})
+}
+
+fn week(product: &Product, params: &Params) -> Result<Vec<WeekRow>> {
+	let weather = &product.weather;
+
+	weather
+		.daily
+		.time
+		.iter()
+		.enumerate()
+		.map(|(i, time)| {
+			let dt = NaiveDate::parse_from_str(time, "%Y-%m-%d")?;
+			let wmo_code = WeatherCode::resolve(
+				weather.daily.weathercode[i],
+				Daylight::Day,
+				&params.texts.weather.weather_code,
+				&params.config.gui,
+			)?;
+			Ok(WeekRow {
+				date: format_date(dt, &params.config.language)?,
+				weather: wmo_code.describe(params.icons),
+				temperature_max: params.format_decimal(weather.daily.temperature_2m_max[i]),
+				temperature_min: params.format_decimal(weather.daily.temperature_2m_min[i]),
+				precipitation: weather.daily.precipitation_probability_max[i].to_string(),
+			})
+		})
+		.collect()
+}
+
+fn historical(product: &Product, params: &Params, date: NaiveDate) -> Result<Overview> {
+	let weather: &OptionalWeather = &product.historical_weather[&date];
+	let (daily, hourly) = (&weather.daily, &weather.hourly);
+	let (lang, t) = (&params.config.language, &params.texts.weather);
+	let units = &params.config.units;
+
+	let (sunrise, sunset) = format_sun_times(weather.sun_times(), units.time)?;
+	let (sunrise_icon, sunset_icon) = sun_icons(params);
+	let wmo_code = WeatherCode::resolve(
+		daily.weathercode.as_ref().unwrap()[0],
+		Daylight::Day,
+		&t.weather_code,
+		&params.config.gui,
+	)?;
+
+	let (temperatures, precipitation, weather_codes) = (
+		hourly.temperature_2m.as_ref().unwrap(),
+		hourly.precipitation.as_ref().unwrap(),
+		hourly.weathercode.as_ref().unwrap(),
+	);
+	let rows = hourly
+		.time
+		.as_deref()
+		.unwrap_or_default()
+		.iter()
+		.enumerate()
+		.map(|(idx, time)| {
+			let hour = hour_of(time);
+			let wmo_code =
+				WeatherCode::resolve(weather_codes[idx], weather.daylight(time), &t.weather_code, &params.config.gui)?;
+			Ok(HourRow {
+				hour,
+				time: format_hour(hour, units.time),
+				weather: wmo_code.describe(params.icons),
+				temperature: params.format_decimal(temperatures[idx]),
+				precipitation: params.format_decimal(precipitation[idx]),
+			})
+		})
+		.collect::<Result<_>>()?;
+
+	Ok(Overview {
+		// Historical dates include the year.
+		date: Some(if lang == "en_US" || lang == "en" {
+			date.format("%a, %-d %b %Y").to_string()
+		} else {
+			format_date(date, lang)?
+		}),
+		weather: wmo_code.describe(params.icons),
+		temperature: format!(
+			"{}/{}{}",
+			params.format_decimal(daily.temperature_2m_max.as_ref().unwrap()[0]),
+			params.format_decimal(daily.temperature_2m_min.as_ref().unwrap()[0]),
+			params.temperature_unit()
+		),
+		precipitation: Some(format!(
+			"{}{}",
+			params.format_decimal(daily.precipitation_sum.as_ref().unwrap()[0]),
+			params.precipitation_unit()
+		)),
+		feels_like: format!(
+			"{} {}/{}{}",
+			t.felt_like,
+			params.format_decimal(daily.apparent_temperature_max.as_ref().unwrap()[0]),
+			params.format_decimal(daily.apparent_temperature_min.as_ref().unwrap()[0]),
+			params.temperature_unit()
+		),
+		details: vec![],
+		sunrise: format!("{sunrise_icon} {sunrise}"),
+		sunset: format!("{sunset_icon} {sunset}"),
+		hours: Some(Hours {
+			heading: t.daily_overview.clone(),
+			temperature_unit: params.temperature_unit().to_string(),
+			precipitation_unit: params.precipitation_unit().to_string(),
+			rows,
+		}),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::display::utils::common_tests::{init_test_params, init_test_product, TEST_PRODUCT};
+	use std::collections::HashSet;
+
+	#[test]
+	fn sections_of_day_and_week() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.config.forecast = HashSet::from([Forecast::day, Forecast::week]);
+
+		let report = Report::new(test_product, &test_params).unwrap();
+
+		assert_eq!(report.sections.len(), 2);
+		let Section::Overview(current) = &report.sections[0] else {
+			panic!("expected the current weather")
+		};
+		assert_eq!(current.date, None);
+		assert_eq!(current.details.len(), 5);
+		assert_eq!(current.hours.as_ref().map(|hours| hours.rows.len()), Some(24));
+		let Section::Week(rows) = &report.sections[1] else {
+			panic!("expected the week")
+		};
+		assert_eq!(rows.len(), 7);
+		assert_eq!(rows[6].date, "Sun, 13 Oct");
+	}
+}
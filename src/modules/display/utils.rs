@@ -1,34 +1,167 @@
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate, NaiveDateTime, Offset};
+use dialoguer::console::style;
 use regex::Regex;
+use std::fmt::Display;
 use unicode_width::UnicodeWidthStr;
 
-use crate::modules::display::product::Product;
+use crate::modules::{
+	display::{gui_config::ThemeColor, product::Product},
+	localization::Locales,
+	params::Params,
+	units::{Speed, Time},
+	weather::Weather,
+};
+
+// Languages that separate decimals with a point instead of a comma.
+const DECIMAL_POINT_LANGUAGES: [&str; 13] =
+	["ar", "en", "ga", "he", "hi", "ja", "ko", "ms", "mt", "ta", "th", "tl", "zh"];
+// Languages written from right to left. Their boxes mirror the order of cells and align texts to the right.
+const RTL_LANGUAGES: [&str; 4] = ["ar", "fa", "he", "ur"];
+// Shown for the time of an event that doesn't happen on a day, e.g., sunrise during polar night.
+pub const NO_TIME: &str = "--:--";
 
 impl Product<'_> {
-	pub fn trunc_address(mut address: String, max_width: usize) -> String {
-		let address_len = address.chars().count();
+	pub fn trunc_address(address: String, max_width: usize) -> String {
+		if display_width(&address) <= max_width {
+			return address;
+		}
+
+		// For most locations with overly long addresses, the results seem to be better if
+		// truncated between the first and second comma instead the penultimate and last comma.
+		// let last_comma = title.matches(',').count();
+		let prep_re = format!("^((?:[^,]*,){{{}}})[^,]*,(.*)", 1);
+		let re = Regex::new(&prep_re).unwrap();
+		let truncated = re.replace(&address, "$1$2").to_string();
+
+		// Addresses without enough commas can't be shortened any further.
+		if truncated == address {
+			return address;
+		}
+
+		Self::trunc_address(truncated, max_width)
+	}
+}
+
+impl Params {
+	// Decimals are displayed with one fractional digit and the separator of the language, e.g.: `12,5` in German.
+	pub fn format_decimal(&self, value: impl Display) -> String {
+		self.localize_decimal(&format!("{value:.1}"))
+	}
+
+	// Wind speed followed by the direction, e.g.: `10.7km/h S`. The Beaufort scale adds its description.
+	pub fn format_wind(&self, speed: f32, direction: &str) -> String {
+		let units = &self.texts.units;
+		match self.config.units.speed {
+			#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+			Speed::beaufort => format!(
+				"{speed}{} {direction}, {}",
+				units.bft,
+				self.texts.weather.beaufort.describe(speed as u8)
+			),
+			unit => format!("{}{} {direction}", self.localize_decimal(&speed.to_string()), units.speed(unit)),
+		}
+	}
+
+	pub fn temperature_unit(&self) -> &str {
+		self.texts.units.temperature(self.config.units.temperature)
+	}
+
+	pub fn precipitation_unit(&self) -> &str {
+		self.texts.units.precipitation(self.config.units.precipitation)
+	}
+
+	pub fn format_pressure(&self, pressure: f32) -> String {
+		format!(
+			"{}{}",
+			self.localize_decimal(&pressure.to_string()),
+			self.texts.units.pressure(self.config.units.pressure)
+		)
+	}
+
+	pub fn format_visibility(&self, visibility: f32) -> String {
+		format!(
+			"{}{}",
+			self.format_decimal(visibility),
+			self.texts.units.distance(self.config.units.distance)
+		)
+	}
 
-		address = if address_len > max_width {
-			// For most locations with overly long addresses, the results seem to be better if
-			// truncated between the first and second comma instead the penultimate and last comma.
-			// let last_comma = title.matches(',').count();
-			let prep_re = format!("^((?:[^,]*,){{{}}})[^,]*,(.*)", 1);
-			let re = Regex::new(&prep_re).unwrap();
+	// A time of the location. With `gui.own_time`, the time in the user's own time zone follows if it differs.
+	pub fn format_clock(&self, weather: &Weather, time: &str) -> Result<String> {
+		let dt = weather.local_datetime(time).with_context(|| format!("Invalid time {time}"))?;
+		let clock = format_clock(dt.naive_local(), self.config.units.time);
+		let own = dt.with_timezone(&Local);
 
-			re.replace(&address, "$1$2").to_string()
+		Ok(if self.config.gui.own_time && own.offset().fix() != *dt.offset() {
+			format!("{clock} ({})", format_clock(own.naive_local(), self.config.units.time))
 		} else {
-			address
+			clock
+		})
+	}
+
+	// Sunrise and sunset of a day, which don't exist during polar day and polar night.
+	pub fn format_sun_times(&self, weather: &Weather, day_index: usize) -> Result<(String, String)> {
+		Ok(match weather.sun_times(day_index) {
+			Some((sunrise, sunset)) => (self.format_clock(weather, sunrise)?, self.format_clock(weather, sunset)?),
+			None => (NO_TIME.to_string(), NO_TIME.to_string()),
+		})
+	}
+
+	// Colors the given temperatures of an already padded text along the gradient, if it's enabled. The colors are
+	// applied last, as they would count towards the width of the text.
+	pub fn paint_temperatures(&self, text: &str, temperatures: &[f32], bold: bool) -> String {
+		let gui = &self.config.gui;
+		let paint = |text: &str, color: ThemeColor| {
+			let styled = color.paint(text);
+			if bold { styled.bold() } else { styled }.to_string()
 		};
+		if !gui.gradient {
+			return if bold { style(text).bold().to_string() } else { text.to_string() };
+		}
 
-		if address_len > max_width {
-			address = Self::trunc_address(address, max_width);
+		let (mut result, mut rest) = (String::new(), text);
+		for &temperature in temperatures {
+			let value = self.format_decimal(temperature);
+			let Some(start) = find_number(rest, &value) else { continue };
+			if start > 0 {
+				result += &paint(&rest[..start], ThemeColor::none);
+			}
+			let celsius = self.config.units.temperature.to_celsius(temperature);
+			result += &paint(&value, gui.gradient(celsius));
+			rest = &rest[start + value.len()..];
+		}
+		if !rest.is_empty() {
+			result += &paint(rest, ThemeColor::none);
 		}
 
-		address
+		result
+	}
+
+	pub fn localize_decimal(&self, number: &str) -> String {
+		let lang = self.config.language.split(['_', '-']).next().unwrap_or_default();
+		if DECIMAL_POINT_LANGUAGES.contains(&lang) {
+			number.to_string()
+		} else {
+			number.replace('.', ",")
+		}
 	}
 }
 
+// Position of a number in a text, skipping matches that are the end of another number, e.g., `1.5` in `11.5`.
+fn find_number(text: &str, number: &str) -> Option<usize> {
+	text.match_indices(number)
+		.map(|(i, _)| i)
+		.find(|&i| !text[..i].ends_with(|c: char| c.is_ascii_digit() || c == '-'))
+}
+
+// Number of terminal columns a string occupies, e.g., CJK characters take two columns and combining marks none.
+pub fn display_width(s: &str) -> usize {
+	s.width()
+}
+
 pub fn pad_string_to_width(s: &str, total_width: usize) -> String {
-	let current_width = s.width(); // Effective width of the string
+	let current_width = display_width(s);
 	if current_width >= total_width {
 		s.to_string() // No padding needed if already wide enough
 	} else {
@@ -37,6 +170,37 @@ pub fn pad_string_to_width(s: &str, total_width: usize) -> String {
 	}
 }
 
+// Pads a cell to its width. Right-to-left cells are aligned to the right.
+pub fn pad_cell(s: &str, total_width: usize, rtl: bool) -> String {
+	let padding = " ".repeat(total_width.saturating_sub(display_width(s)));
+	if rtl {
+		padding + s
+	} else {
+		s.to_string() + &padding
+	}
+}
+
+// Joins already padded (and possibly styled) cells. In right-to-left mode the first cell is placed at the right end.
+pub fn mirror_cells<T: ToString>(cells: &[T], rtl: bool) -> String {
+	let cells = cells.iter().map(ToString::to_string);
+	if rtl {
+		cells.rev().collect()
+	} else {
+		cells.collect()
+	}
+}
+
+pub fn align_cells(cells: &[(&str, usize)], rtl: bool) -> String {
+	mirror_cells(
+		&cells.iter().map(|(s, width)| pad_cell(s, *width, rtl)).collect::<Vec<_>>(),
+		rtl,
+	)
+}
+
+pub fn is_rtl(lang: &str) -> bool {
+	RTL_LANGUAGES.contains(&lang.split(['_', '-']).next().unwrap_or_default())
+}
+
 pub fn style_number(mut num: i32, sub: bool) -> String {
 	const SUPERSCRIPT_DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
 	const SUBSCRIPT_DIGITS: [char; 10] = ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'];
@@ -70,12 +234,50 @@ pub fn style_number(mut num: i32, sub: bool) -> String {
 	result
 }
 
+pub fn format_sun_times(sun_times: Option<(&str, &str)>, unit: Time) -> Result<(String, String)> {
+	Ok(match sun_times {
+		Some((sunrise, sunset)) => (format_time(sunrise, unit)?, format_time(sunset, unit)?),
+		None => (NO_TIME.to_string(), NO_TIME.to_string()),
+	})
+}
+
+pub fn format_time(time: &str, unit: Time) -> Result<String> {
+	Ok(format_clock(NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M")?, unit))
+}
+
+fn format_clock(dt: NaiveDateTime, unit: Time) -> String {
+	match unit {
+		Time::am_pm => dt.format("%-I:%M%P").to_string(),
+		Time::military => dt.format("%H:%M").to_string(),
+	}
+}
+
+pub fn format_hour(hour: usize, unit: Time) -> String {
+	match unit {
+		Time::am_pm => format!(
+			"{}{}",
+			if hour % 12 == 0 { 12 } else { hour % 12 },
+			if hour < 12 { "am" } else { "pm" }
+		),
+		Time::military => format!("{hour:02}:00"),
+	}
+}
+
+pub fn format_date(dt: NaiveDate, lang: &str) -> Result<String> {
+	if lang == "en_US" || lang == "en" {
+		Ok(dt.format("%a, %e %b").to_string())
+	} else {
+		Locales::localize_date(dt, lang)
+	}
+}
+
 #[cfg(test)]
 pub mod common_tests {
 	use crate::modules::config;
-	use crate::modules::display::product::Product;
+	use crate::modules::display::{hourly, product::Product};
 	use crate::modules::localization;
 	use crate::modules::params::Params;
+	use crate::modules::units;
 	use crate::modules::weather;
 	use std::collections::{HashMap, HashSet};
 	use std::sync::OnceLock;
@@ -105,6 +307,9 @@ pub mod common_tests {
 					precipitation: String::from("mm"),
 				},
 				hourly: weather::Hourly {
+					time: (7..14)
+						.flat_map(|day| (0..24).map(move |hour| format!("2024-10-{day:02}T{hour:02}:00")))
+						.collect(),
 					temperature_2m: vec![
 						8.6, 8.2, 8.0, 7.8, 7.5, 7.2, 7.0, 6.8, 6.8, 7.3, 8.7, 10.6, 12.4, 13.7, 14.7, 15.1, 15.0,
 						14.6, 14.4, 14.1, 13.9, 13.6, 13.4, 13.1, 12.7, 12.5, 12.3, 12.1, 12.0, 12.0, 12.2, 12.3, 12.6,
@@ -199,6 +404,9 @@ pub mod common_tests {
 						1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 61, 61,
 						61, 61, 61, 61, 61, 61, 61, 80, 80, 80, 61, 61, 61, 0, 0, 0, 0, 0, 0,
 					],
+					visibility: vec![24.1; 7 * 24],
+					windspeed_10m: (0..7 * 24_u8).map(|h| 4.0 + f32::from(h % 24) / 2.0).collect(),
+					uv_index: vec![0.0; 7 * 24],
 				},
 				daily_units: weather::DailyUnits {
 					temperature_2m_max: String::from("°C"),
@@ -240,6 +448,11 @@ pub mod common_tests {
 					precipitation_probability_max: vec![25, 88, 98, 50, 21, 11, 32],
 					precipitation_sum: None,
 				},
+				latitude: 52.52,
+				longitude: 13.41,
+				timezone: String::from("Europe/Berlin"),
+				utc_offset_seconds: 7200,
+				past_days: 0,
 			},
 			historical_weather: HashMap::new(),
 		}
@@ -247,9 +460,90 @@ pub mod common_tests {
 
 	pub fn init_test_params() -> Params {
 		Params {
-			config: config::Config::default(),
+			// The weather data of the test product is in metric units.
+			config: config::Config {
+				units: units::Units::default(),
+				..Default::default()
+			},
+			stored_config: config::Config::default(),
 			texts: localization::Locales::default(),
 			historical_weather: HashSet::new(),
+			days: Vec::new(),
+			output: None,
+			icons: false,
+			layout: hourly::Layout::default(),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::weather::hours_of_day;
+
+	#[test]
+	fn painted_temperatures() {
+		assert_eq!(find_number("Overcast, 11.5/1.5°C", "1.5"), Some(15));
+		assert_eq!(find_number("Feels like -1.5°C", "1.5"), None);
+		assert_eq!(find_number("Feels like -1.5°C", "-1.5"), Some(11));
+
+		let mut test_params = common_tests::init_test_params();
+		test_params.config.gui.gradient = true;
+		let text = "Overcast, 15.1/6.8°C  ";
+		let painted = test_params.paint_temperatures(text, &[15.1, 6.8], true);
+		assert_eq!(strip_ansi_escapes::strip_str(painted), text);
+	}
+
+	#[test]
+	fn wide_characters() {
+		assert_eq!(display_width("東京, 日本"), 10);
+		assert_eq!(pad_string_to_width("東京", 6), "東京  ");
+		assert_eq!(Product::trunc_address("東京都, 千代田区, 日本".to_string(), 12), "東京都, 日本");
+		assert_eq!(Product::trunc_address("Berlin".to_string(), 3), "Berlin");
+	}
+
+	#[test]
+	fn right_to_left_cells() {
+		assert!(is_rtl("ar_EG") && is_rtl("he"));
+		assert!(!is_rtl("de_DE"));
+
+		let cells = [("שלום", 6), ("1", 3)];
+		assert_eq!(align_cells(&cells, false), "שלום  1  ");
+		assert_eq!(align_cells(&cells, true), "  1  שלום");
+	}
+
+	#[test]
+	fn daylight_saving_time() {
+		let mut weather = common_tests::init_test_product().weather;
+		weather.hourly.time = ["2024-10-27T01:00", "2024-10-27T02:00", "2024-10-27T02:00", "2024-10-27T03:00"]
+			.map(String::from)
+			.to_vec();
+		assert_eq!(hours_of_day(&weather.hourly.time, "2024-10-27"), 0..4);
+		assert_eq!(hours_of_day(&weather.hourly.time, "2024-10-28"), 4..4);
+
+		// Clocks are turned back at 3:00 summer time.
+		let offset = |weather: &Weather, time| weather.local_datetime(time).unwrap().offset().local_minus_utc();
+		assert_eq!(offset(&weather, "2024-10-27T01:00"), 7200);
+		assert_eq!(offset(&weather, "2024-10-27T03:00"), 3600);
+
+		// Without a time zone name, the offset of the response is used.
+		weather.timezone = String::new();
+		assert_eq!(offset(&weather, "2024-10-27T03:00"), 7200);
+	}
+
+	#[test]
+	fn polar_sun_times() {
+		let mut weather = common_tests::init_test_product().weather;
+		assert_eq!(weather.sun_times(0), Some(("2024-10-07T07:18", "2024-10-07T18:29")));
+		assert_eq!(format_sun_times(weather.sun_times(0), Time::am_pm).unwrap().1, "6:29pm");
+
+		// The sun doesn't rise during polar night.
+		weather.daily.sunrise[0] = String::from("2024-10-08T00:00");
+		weather.daily.sunset[0] = String::from("2024-10-08T00:00");
+		assert_eq!(weather.sun_times(0), None);
+		assert_eq!(
+			format_sun_times(weather.sun_times(0), Time::military).unwrap(),
+			(NO_TIME.to_string(), NO_TIME.to_string())
+		);
+	}
+}
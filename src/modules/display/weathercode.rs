@@ -1,49 +1,64 @@
 use anyhow::{bail, Result};
 
-use crate::modules::localization::WeatherCodeLocales;
+use crate::modules::{localization::WeatherCodeLocales, sun::Daylight};
+
+use super::gui_config::Gui;
 
 pub struct WeatherCode {
 	pub interpretation: String,
-	pub icon: char,
+	pub icon: String,
 }
 
 impl WeatherCode {
-	pub fn resolve(weather_code: u8, is_night: bool, t: &WeatherCodeLocales) -> Result<Self> {
-		let res = match weather_code {
-			0 => (&t.clear_sky, if is_night { '' } else { '' }),
-			1 => (&t.mostly_clear, if is_night { '' } else { '' }),
-			2 => (&t.partly_cloudy, if is_night { '' } else { '' }),
-			3 => (&t.overcast, ''),
-			45 => (&t.fog, if is_night { '' } else { '' }),
-			48 => (&t.depositing_rime_fog, ''),
-			51 => (&t.light_drizzle, if is_night { '' } else { '' }),
-			53 => (&t.moderate_drizzle, if is_night { '' } else { '' }),
-			55 => (&t.dense_drizzle, if is_night { '' } else { '' }),
-			56 => (&t.light_freezing_drizzle, if is_night { '' } else { '󰼵' }),
-			57 => (&t.dense_freezing_drizzle, if is_night { '' } else { '󰙿' }),
-			61 => (&t.slight_rain, if is_night { '' } else { '' }),
-			63 => (&t.moderate_rain, if is_night { '' } else { '' }),
-			65 => (&t.heavy_rain, if is_night { '' } else { '' }),
-			66 => (&t.light_freezing_rain, if is_night { '' } else { '' }),
-			67 => (&t.heavy_freezing_rain, if is_night { '' } else { '' }),
-			71 => (&t.slight_snow_fall, if is_night { '' } else { '' }),
-			73 => (&t.moderate_snow_fall, if is_night { '' } else { '' }),
-			75 => (&t.heavy_snow_fall, if is_night { '' } else { '' }),
-			77 => (&t.snow_grains, ''),
-			80 => (&t.slight_rain_showers, if is_night { '' } else { '' }),
-			81 => (&t.moderate_rain_showers, if is_night { '' } else { '' }),
-			82 => (&t.violent_rain_showers, if is_night { '' } else { '' }),
-			85 => (&t.slight_snow_showers, if is_night { '' } else { '' }),
-			86 => (&t.heavy_snow_showers, if is_night { '' } else { '' }),
-			95 => (&t.thunderstorm, if is_night { '' } else { '' }),
-			96 => (&t.thunderstorm_slight_hail, if is_night { '' } else { '' }),
-			99 => (&t.thunderstorm_heavy_hail, if is_night { '' } else { '' }),
+	// Icons come from the configured set unless the config overrides the one of the code.
+	pub fn resolve(weather_code: u8, daylight: Daylight, t: &WeatherCodeLocales, gui: &Gui) -> Result<Self> {
+		let interpretation = match weather_code {
+			0 => &t.clear_sky,
+			1 => &t.mostly_clear,
+			2 => &t.partly_cloudy,
+			3 => &t.overcast,
+			45 => &t.fog,
+			48 => &t.depositing_rime_fog,
+			51 => &t.light_drizzle,
+			53 => &t.moderate_drizzle,
+			55 => &t.dense_drizzle,
+			56 => &t.light_freezing_drizzle,
+			57 => &t.dense_freezing_drizzle,
+			61 => &t.slight_rain,
+			63 => &t.moderate_rain,
+			65 => &t.heavy_rain,
+			66 => &t.light_freezing_rain,
+			67 => &t.heavy_freezing_rain,
+			71 => &t.slight_snow_fall,
+			73 => &t.moderate_snow_fall,
+			75 => &t.heavy_snow_fall,
+			77 => &t.snow_grains,
+			80 => &t.slight_rain_showers,
+			81 => &t.moderate_rain_showers,
+			82 => &t.violent_rain_showers,
+			85 => &t.slight_snow_showers,
+			86 => &t.heavy_snow_showers,
+			95 => &t.thunderstorm,
+			96 => &t.thunderstorm_slight_hail,
+			99 => &t.thunderstorm_heavy_hail,
 			_ => bail!("Unknown weather code"),
 		};
+		let icon = match gui.icon_overrides.get(&weather_code) {
+			Some(icon) => icon.clone(),
+			None => gui.icons.weather(weather_code, daylight).to_string(),
+		};
 
 		Ok(Self {
-			interpretation: res.0.to_string(),
-			icon: res.1,
+			interpretation: interpretation.clone(),
+			icon,
 		})
 	}
+
+	pub fn describe(&self, with_icon: bool) -> String {
+		if with_icon {
+			format!("{} {}", self.icon, self.interpretation)
+		} else {
+			self.interpretation.clone()
+		}
+	}
 }
@@ -1,26 +1,42 @@
 use anyhow::Result;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use unicode_width::UnicodeWidthStr;
 
-use crate::modules::{localization::Locales, params::Params};
+use crate::modules::{localization::Locales, params::Params, sun::Daylight};
 
 use super::{
 	border::{Border, BorderStyle, Edge, Separator},
 	current::Dimensions,
 	gui_config::ConfigurableColor,
+	hourly::Layout,
+	icons::Icon,
 	product::{Product, MIN_CELL_WIDTH, TOTAL_BORDER_PADDING},
-	utils::pad_string_to_width,
+	utils::{align_cells, display_width, is_rtl, mirror_cells, pad_cell, pad_string_to_width},
 	weathercode::WeatherCode,
 };
 
+// Narrower bars can't show the trend of the week.
+const MIN_BAR_WIDTH: usize = 10;
+
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum WeekStyle {
+	// Columns of the date, the temperatures and the interpretation
+	#[default]
+	text,
+	// Bars of the temperature ranges on a scale shared by the week
+	bars,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ForecastDay {
 	pub date: String,
+	pub icon: String,
 	pub weather: String,
 	pub interpretation: String,
 }
 
+#[allow(clippy::too_many_lines)]
 pub fn prep(product: &Product, params: &Params, current_dimensions: Option<Dimensions>) -> Result<Vec<String>> {
 	let (lang, t) = (&params.config.language, &params.texts.weather);
 	let mut days = Vec::new();
@@ -36,23 +52,29 @@ pub fn prep(product: &Product, params: &Params, current_dimensions: Option<Dimen
 			Locales::localize_date(dt, lang)?
 		};
 
-		let weather_code = WeatherCode::resolve(product.weather.daily.weathercode[i], false, &t.weather_code)?;
+		let weather_code = WeatherCode::resolve(
+			product.weather.daily.weathercode[i],
+			Daylight::Day,
+			&t.weather_code,
+			&params.config.gui,
+		)?;
 		let weather = format!(
-			"{} {:.1}{}/{:.1}{}",
+			"{} {}{}/{}{}",
 			weather_code.icon,
-			product.weather.daily.temperature_2m_max[i],
-			product.weather.daily_units.temperature_2m_max,
-			product.weather.daily.temperature_2m_min[i],
-			product.weather.daily_units.temperature_2m_min,
+			params.format_decimal(product.weather.daily.temperature_2m_max[i]),
+			params.temperature_unit(),
+			params.format_decimal(product.weather.daily.temperature_2m_min[i]),
+			params.temperature_unit(),
 		);
-		let day_width = format!("{}{}{}", date, weather, weather_code.interpretation).width();
+		let day_width = display_width(&format!("{}{}{}", date, weather, weather_code.interpretation));
 		if day_width > width {
 			width = day_width;
 		}
 
 		let day: ForecastDay = {
 			ForecastDay {
-				date: date.to_string(),
+				date: date.clone(),
+				icon: weather_code.icon.clone(),
 				weather,
 				interpretation: weather_code.interpretation,
 			}
@@ -61,42 +83,101 @@ pub fn prep(product: &Product, params: &Params, current_dimensions: Option<Dimen
 		days.push(day);
 	}
 
-	// let forecast = self;
-	let gui = &params.config.gui;
+	let (gui, rtl) = (&params.config.gui, is_rtl(lang));
 
 	let (mut width, mut cell_width) = (width + 10, MIN_CELL_WIDTH);
 	if let Some(dims) = current_dimensions {
 		cell_width = std::cmp::max(cell_width, dims.cell_width);
 		width = std::cmp::max(width, dims.width);
 	}
+	// Narrow terminals get the width of the condensed hourly forecast, the icons stand in for the interpretations.
+	let condensed = params.layout == Layout::Condensed && width > Layout::Condensed.width();
+	if condensed {
+		width = Layout::Condensed.width();
+	}
+	// The cells around the bars are as wide as their widest value.
+	let daily = &product.weather.daily;
+	let lows = daily
+		.temperature_2m_min
+		.iter()
+		.map(|t| format!("{}{}", params.format_decimal(*t), params.temperature_unit()))
+		.collect::<Vec<_>>();
+	let highs = daily
+		.temperature_2m_max
+		.iter()
+		.map(|t| format!("{}{}", params.format_decimal(*t), params.temperature_unit()))
+		.collect::<Vec<_>>();
+	let precipitation = daily
+		.precipitation_probability_max
+		.iter()
+		.map(|p| format!("{p}{}", params.config.gui.icons.get(Icon::Probability)))
+		.collect::<Vec<_>>();
+	let widest = |cells: &[String]| cells.iter().map(|c| display_width(c)).max().unwrap_or_default();
+	let bar_cells = [cell_width, 2, widest(&lows) + 1, widest(&highs) + 1, widest(&precipitation) + 1];
+	if gui.week == WeekStyle::bars {
+		width = width.max(bar_cells.iter().sum::<usize>() + MIN_BAR_WIDTH + TOTAL_BORDER_PADDING);
+	}
 	let width_no_border_pad = width - TOTAL_BORDER_PADDING;
+	let bar_width = width_no_border_pad.saturating_sub(bar_cells.iter().sum());
+	let scale = (
+		daily.temperature_2m_min.iter().copied().fold(f32::INFINITY, f32::min),
+		daily.temperature_2m_max.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+	);
 
 	let mut result = Vec::<String>::new();
 
 	// Border Top
-	result.push(format!(
-		"{}",
-		&Edge::Top.fmt(width, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", &Edge::Top.fmt(width, gui.border).border_color(gui)));
 
 	let mut chunks = days.chunks(1).peekable();
 
 	let mut n = 0;
 	while let Some(_) = chunks.next() {
-		let forecast_day = format!(
-			"{}{}{}",
-			pad_string_to_width(&days[n].date, cell_width),
-			pad_string_to_width(
-				&days[n].weather,
-				width_no_border_pad - days[n].interpretation.width() - cell_width
-			),
-			days[n].interpretation,
-		);
+		let forecast_day = if gui.week == WeekStyle::bars {
+			let bar = prepare_bar(
+				(daily.temperature_2m_min[n], daily.temperature_2m_max[n]),
+				scale,
+				bar_width,
+				params,
+				rtl,
+			);
+			mirror_cells(
+				&[
+					pad_cell(&days[n].date, bar_cells[0], rtl),
+					pad_cell(&days[n].icon, bar_cells[1], rtl),
+					format!("{: >w$} ", lows[n], w = bar_cells[2] - 1),
+					bar,
+					format!(" {: <w$}", highs[n], w = bar_cells[3] - 1),
+					format!("{: >w$}", precipitation[n], w = bar_cells[4])
+						.precipitation_color(gui)
+						.to_string(),
+				],
+				rtl,
+			)
+		} else if condensed {
+			align_cells(
+				&[
+					(&days[n].date, cell_width),
+					(&days[n].weather, width_no_border_pad - cell_width),
+				],
+				rtl,
+			)
+		} else {
+			let interpretation_width = display_width(&days[n].interpretation);
+			align_cells(
+				&[
+					(&days[n].date, cell_width),
+					(&days[n].weather, width_no_border_pad - interpretation_width - cell_width),
+					(&days[n].interpretation, interpretation_width),
+				],
+				rtl,
+			)
+		};
 		result.push(format!(
 			"{} {} {}",
-			&Border::L.fmt(gui.border).plain_or_bright_black(gui.color),
+			&Border::L.fmt(gui.border).border_color(gui),
 			pad_string_to_width(&forecast_day, width_no_border_pad),
-			&Border::R.fmt(gui.border).plain_or_bright_black(gui.color),
+			&Border::R.fmt(gui.border).border_color(gui),
 		));
 		if chunks.peek().is_some() {
 			result.push(format!(
@@ -106,7 +187,7 @@ pub fn prep(product: &Product, params: &Params, current_dimensions: Option<Dimen
 					BorderStyle::solid => Separator::Solid.fmt(width, gui.border),
 					_ => Separator::Dashed.fmt(width, gui.border),
 				}
-				.plain_or_bright_black(gui.color)
+				.border_color(gui)
 			));
 		}
 
@@ -114,14 +195,64 @@ pub fn prep(product: &Product, params: &Params, current_dimensions: Option<Dimen
 	}
 
 	// Border Bottom
-	result.push(format!(
-		"{}",
-		Edge::Bottom.fmt(width, gui.border).plain_or_bright_black(gui.color)
-	));
+	result.push(format!("{}", Edge::Bottom.fmt(width, gui.border).border_color(gui)));
 
 	Ok(result)
 }
 
+// A bar of the range between the low and the high on the scale of the week, drawn in half cells and colored by the
+// temperature. The rest of the scale is a thin track.
+#[allow(
+	clippy::cast_precision_loss,
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss
+)]
+fn prepare_bar(range: (f32, f32), scale: (f32, f32), width: usize, params: &Params, rtl: bool) -> String {
+	let (gui, unit) = (&params.config.gui, params.config.units.temperature);
+	let halves = (width * 2) as f32;
+	let position = |t: f32| {
+		if scale.1 > scale.0 {
+			((t - scale.0) / (scale.1 - scale.0) * (halves - 1.0)).round() as usize
+		} else {
+			0
+		}
+	};
+	let (start, end) = (position(range.0), position(range.1));
+
+	let mut cells = (0..width)
+		.map(|cell| {
+			let (left, right) = ((start..=end).contains(&(cell * 2)), (start..=end).contains(&(cell * 2 + 1)));
+			// The temperature at the middle of the cell
+			let t = scale.0 + (scale.1 - scale.0) * (cell as f32 + 0.5) / width as f32;
+			match (left, right) {
+				(true, true) => ('━', Some(t)),
+				(true, false) => ('╸', Some(t)),
+				(false, true) => ('╺', Some(t)),
+				(false, false) => ('─', None),
+			}
+		})
+		.collect::<Vec<_>>();
+	// Right-to-left scales start with the lowest temperature on the right.
+	if rtl {
+		cells.reverse();
+		for cell in &mut cells {
+			cell.0 = match cell.0 {
+				'╸' => '╺',
+				'╺' => '╸',
+				c => c,
+			};
+		}
+	}
+
+	cells
+		.into_iter()
+		.map(|(glyph, t)| match t {
+			None => glyph.to_string().as_str().border_color(gui).to_string(),
+			Some(t) => gui.temperature_color(unit.to_celsius(t)).paint(&glyph.to_string()).to_string(),
+		})
+		.collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -156,4 +287,60 @@ mod tests {
 		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
 		assert_eq!(want, have);
 	}
+
+	#[test]
+	fn condensed_output() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.layout = Layout::fit(60);
+
+		let want = "\
+╭────────────────────────────────────────────────╮
+│ Mon,  7 Oct     15.1°C/6.8°C                  │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Tue,  8 Oct     20.3°C/12.0°C                 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Wed,  9 Oct     17.9°C/13.7°C                 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Thu, 10 Oct     20.2°C/13.7°C                 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Fri, 11 Oct     13.2°C/7.9°C                  │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Sat, 12 Oct     12.2°C/4.8°C                  │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Sun, 13 Oct     10.9°C/5.6°C                  │
+╰────────────────────────────────────────────────╯";
+
+		let lines = prep(test_product, &test_params, None).unwrap();
+		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
+		assert_eq!(want, have);
+	}
+
+	#[test]
+	fn bars_output() {
+		let test_product = TEST_PRODUCT.get_or_init(init_test_product);
+		let mut test_params = init_test_params();
+		test_params.config.gui.week = WeekStyle::bars;
+
+		let want = "\
+╭──────────────────────────────────────────────────────╮
+│ Mon,  7 Oct      6.8°C ──━━━━━━━━━╸───── 15.1°C 25󰖎 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Tue,  8 Oct     12.0°C ───────╺━━━━━━━━━ 20.3°C 88󰖎 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Wed,  9 Oct     13.7°C ─────────╺━━━━╸── 17.9°C 98󰖎 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Thu, 10 Oct     13.7°C ─────────╺━━━━━━━ 20.2°C 50󰖎 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Fri, 11 Oct      7.9°C ───╺━━━━━╸─────── 13.2°C 21󰖎 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Sat, 12 Oct      4.8°C ━━━━━━━━╸──────── 12.2°C 11󰖎 │
+├┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┤
+│ Sun, 13 Oct      5.6°C ─━━━━━━────────── 10.9°C 32󰖎 │
+╰──────────────────────────────────────────────────────╯";
+
+		let lines = prep(test_product, &test_params, None).unwrap();
+		let have = strip_ansi_escapes::strip_str(lines.join("\n"));
+		assert_eq!(want, have);
+	}
 }
@@ -1,7 +1,8 @@
 use anyhow::{bail, Result};
-use strum_macros::Display;
 
-#[derive(Display)]
+use crate::modules::localization::WindDirectionLocales;
+
+#[derive(Clone, Copy)]
 pub enum WindDirection {
 	NW,
 	N,
@@ -30,16 +31,38 @@ impl WindDirection {
 		Ok(direction)
 	}
 
-	pub const fn get_icon(&self) -> char {
-		match *self {
-			Self::N => '↓',
-			Self::NE => '↙',
-			Self::E => '←',
-			Self::SE => '↖',
-			Self::S => '↑',
-			Self::SW => '↗',
-			Self::W => '→',
-			Self::NW => '↘',
+	pub fn localize(self, t: &WindDirectionLocales) -> &str {
+		match self {
+			Self::N => &t.n,
+			Self::NE => &t.ne,
+			Self::E => &t.e,
+			Self::SE => &t.se,
+			Self::S => &t.s,
+			Self::SW => &t.sw,
+			Self::W => &t.w,
+			Self::NW => &t.nw,
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::modules::localization::Locales;
+
+	#[test]
+	fn localized_directions() {
+		let de = serde_json::from_str::<Locales>(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/de.json")))
+			.unwrap();
+
+		let directions = [0.0, 45.0, 90.0, 135.0, 180.0, 225.0, 270.0, 315.0, 359.0].map(|degrees| {
+			WindDirection::get_direction(degrees)
+				.unwrap()
+				.localize(&de.weather.wind_direction)
+				.to_string()
+		});
+
+		assert_eq!(directions, ["N", "NO", "O", "SO", "S", "SW", "W", "NW", "N"]);
+		assert_eq!(WindDirection::NE.localize(&Locales::default().weather.wind_direction), "NE");
+	}
+}
@@ -1,40 +1,62 @@
-#[allow(unused_imports)]
-use chrono::{Datelike, Local, Weekday};
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, NaiveDate, Weekday};
 use std::collections::HashSet;
 
-use super::args::Forecast;
+use super::{
+	args::{Day, Forecast},
+	params::Params,
+	weather::Weather,
+};
 
-pub fn get_indices(forecast: &HashSet<Forecast>, curr_day: Weekday) -> Vec<usize> {
-	let dist_from_ref_day = curr_day.number_from_monday();
+// Limits of open-meteo's forecast API.
+pub const MAX_FORECAST_DAYS: u8 = 16;
+pub const MAX_PAST_DAYS: u8 = 92;
 
-	// Indices for forecasts that should be rendered. 7 will be used as a special value
-	// [0] = current day; [1..7] = week days; [7] = week overview
-	// Until there is a more concise solution this is a working and fairly slim approach.
-	let mut forecast_indices: Vec<usize> = forecast
+// Indices of the daily values of the days that should be rendered. The week overview isn't one of them.
+pub fn get_indices(params: &Params, weather: &Weather) -> Result<Vec<usize>> {
+	let today = NaiveDate::parse_from_str(&weather.current_weather.time, "%Y-%m-%dT%H:%M")?;
+	get_offsets(&params.config.forecast, &params.days, today)
+		.into_iter()
+		.map(|offset| {
+			weather
+				.day_index(offset)
+				.ok_or_else(|| anyhow!(params.texts.errors.day_out_of_range.clone()))
+		})
+		.collect()
+}
+
+// Distances of the requested days from today. Weekdays refer to the next seven days.
+pub fn get_offsets(forecast: &HashSet<Forecast>, days: &[Day], today: NaiveDate) -> Vec<i64> {
+	let dist_from_ref_day = today.weekday().number_from_monday();
+
+	let mut offsets: Vec<i64> = forecast
 		.iter()
-		.map(|val| match val {
-			Forecast::week => 7,
-			Forecast::mo => get_day_index(dist_from_ref_day, Weekday::Mon),
-			Forecast::tu => get_day_index(dist_from_ref_day, Weekday::Tue),
-			Forecast::we => get_day_index(dist_from_ref_day, Weekday::Wed),
-			Forecast::th => get_day_index(dist_from_ref_day, Weekday::Thu),
-			Forecast::fr => get_day_index(dist_from_ref_day, Weekday::Fri),
-			Forecast::sa => get_day_index(dist_from_ref_day, Weekday::Sat),
-			Forecast::su => get_day_index(dist_from_ref_day, Weekday::Sun),
-			Forecast::tomorrow => get_day_index(dist_from_ref_day, Local::now().weekday().succ()),
-			_ => 0,
+		.filter_map(|val| match val {
+			Forecast::disable | Forecast::week => None,
+			Forecast::day => Some(0),
+			Forecast::tomorrow => Some(1),
+			Forecast::mo => Some(get_day_index(dist_from_ref_day, Weekday::Mon)),
+			Forecast::tu => Some(get_day_index(dist_from_ref_day, Weekday::Tue)),
+			Forecast::we => Some(get_day_index(dist_from_ref_day, Weekday::Wed)),
+			Forecast::th => Some(get_day_index(dist_from_ref_day, Weekday::Thu)),
+			Forecast::fr => Some(get_day_index(dist_from_ref_day, Weekday::Fri)),
+			Forecast::sa => Some(get_day_index(dist_from_ref_day, Weekday::Sat)),
+			Forecast::su => Some(get_day_index(dist_from_ref_day, Weekday::Sun)),
 		})
+		.chain(days.iter().map(|day| match day {
+			Day::Relative(offset) => *offset,
+			Day::Date(date) => date.signed_duration_since(today).num_days(),
+		}))
 		.collect();
 
-	forecast_indices.sort_unstable();
-	forecast_indices
+	offsets.sort_unstable();
+	offsets.dedup();
+	offsets
 }
 
 // Get the index of a requested day to navigate the api response based on the distance from the current day.
-fn get_day_index(dist_from_ref_day: u32, forecast_day: Weekday) -> usize {
-	(((forecast_day.number_from_monday() as i8 - dist_from_ref_day as i8) % 7 + 7) % 7)
-		.try_into()
-		.unwrap()
+fn get_day_index(dist_from_ref_day: u32, forecast_day: Weekday) -> i64 {
+	(((forecast_day.number_from_monday() as i8 - dist_from_ref_day as i8) % 7 + 7) % 7).into()
 }
 
 #[cfg(test)]
@@ -61,17 +83,30 @@ mod tests {
 	}
 
 	#[test]
-	fn forecast_indices() {
-		let curr_day = Weekday::Mon;
+	fn forecast_offsets() {
+		// Monday
+		let today = NaiveDate::from_ymd_opt(2024, 10, 7).unwrap();
 		// Test weekday forcast for same day. E.g. `-f mo` on a monday.
 		assert!(
-			get_indices(&HashSet::from([Forecast::mo]), curr_day)
-				== get_indices(&HashSet::from([Forecast::day]), curr_day)
+			get_offsets(&HashSet::from([Forecast::mo]), &[], today)
+				== get_offsets(&HashSet::from([Forecast::day]), &[], today)
 		);
-		// Test week overview `-f w`.
-		assert!(get_indices(&HashSet::from([Forecast::week]), curr_day) == [7]);
+		// The week overview `-f w` isn't a day.
+		assert!(get_offsets(&HashSet::from([Forecast::week]), &[], today).is_empty());
 		// Test distance from current day until requested day.
-		assert!(get_indices(&HashSet::from([Forecast::tu, Forecast::we, Forecast::sa]), curr_day) == [1, 2, 5]);
-		assert!(get_indices(&HashSet::from([Forecast::day, Forecast::week]), curr_day) == [0, 7]);
+		assert!(get_offsets(&HashSet::from([Forecast::tu, Forecast::we, Forecast::sa]), &[], today) == [1, 2, 5]);
+		assert!(get_offsets(&HashSet::from([Forecast::day, Forecast::week]), &[], today) == [0]);
+		// Days relative to today and dates, e.g. `-f +10,2024-10-05,tu`.
+		assert!(
+			get_offsets(
+				&HashSet::from([Forecast::tu]),
+				&[
+					Day::Relative(10),
+					Day::Date(NaiveDate::from_ymd_opt(2024, 10, 5).unwrap()),
+					Day::Relative(1)
+				],
+				today
+			) == [-2, 1, 10]
+		);
 	}
 }
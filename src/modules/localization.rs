@@ -1,29 +1,33 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use chrono::{Local, NaiveDate};
 use futures::{stream::FuturesOrdered, TryStreamExt};
-use optional_struct::{optional_struct, Applicable};
-use reqwest::Url;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::{
 	fs::{self, File},
 	io::Write,
 	path::PathBuf,
 };
 
-#[optional_struct(LocalesFile)]
+use super::{
+	config::Config,
+	display::graph::Series,
+	moon::Phase,
+	translation::Translation,
+	units::{Distance, Precipitation, Pressure, Speed, Temperature},
+};
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
-#[allow(clippy::unsafe_derive_deserialize)]
 pub struct Locales {
 	pub greeting: String,
 	pub search_station: String,
-	#[optional_rename(ConfigLocalesFile)]
+	pub disclaimer: String,
 	pub config: ConfigLocales,
-	#[optional_rename(WeatherLocalesFile)]
 	pub weather: WeatherLocales,
+	pub units: UnitLocales,
+	pub errors: ErrorLocales,
 }
 
-#[optional_struct(ConfigLocalesFile)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ConfigLocales {
 	pub confirm: String,
@@ -35,7 +39,6 @@ pub struct ConfigLocales {
 	pub no_selection: String,
 }
 
-#[optional_struct(WeatherLocalesFile)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WeatherLocales {
 	pub feels_like: String,
@@ -43,12 +46,115 @@ pub struct WeatherLocales {
 	pub humidity: String,
 	pub dew_point: String,
 	pub hourly_forecast: String,
+	pub next_hours: PluralLocales,
 	pub daily_overview: String,
-	#[optional_rename(WeatherCodeLocalesFile)]
+	pub visibility: String,
+	pub wind_direction: WindDirectionLocales,
+	pub beaufort: BeaufortLocales,
+	pub series: SeriesLocales,
+	pub astronomy: AstronomyLocales,
 	pub weather_code: WeatherCodeLocales,
 }
 
-#[optional_struct(WeatherCodeLocalesFile)]
+// Abbreviations of the cardinal and intercardinal directions.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WindDirectionLocales {
+	pub n: String,
+	pub ne: String,
+	pub e: String,
+	pub se: String,
+	pub s: String,
+	pub sw: String,
+	pub w: String,
+	pub nw: String,
+}
+
+// Descriptions of the Beaufort scale from 0 to 12.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BeaufortLocales {
+	pub calm: String,
+	pub light_air: String,
+	pub light_breeze: String,
+	pub gentle_breeze: String,
+	pub moderate_breeze: String,
+	pub fresh_breeze: String,
+	pub strong_breeze: String,
+	pub near_gale: String,
+	pub gale: String,
+	pub strong_gale: String,
+	pub storm: String,
+	pub violent_storm: String,
+	pub hurricane: String,
+}
+
+// Names of the series in the hourly graph.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SeriesLocales {
+	pub temperature: String,
+	pub precipitation: String,
+	pub wind: String,
+	pub humidity: String,
+	pub pressure: String,
+	pub uv_index: String,
+}
+
+// Phases of the moon and the rows of the astronomy panel.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AstronomyLocales {
+	pub new_moon: String,
+	pub waxing_crescent: String,
+	pub first_quarter: String,
+	pub waxing_gibbous: String,
+	pub full_moon: String,
+	pub waning_gibbous: String,
+	pub third_quarter: String,
+	pub waning_crescent: String,
+	pub day_length: String,
+	pub solar_noon: String,
+	pub golden_hour: String,
+	pub blue_hour: String,
+}
+
+// A text that depends on a count. `{n}` is replaced with the count.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PluralLocales {
+	pub one: String,
+	pub other: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UnitLocales {
+	pub celsius: String,
+	pub fahrenheit: String,
+	pub kelvin: String,
+	pub mm: String,
+	pub inch: String,
+	pub kmh: String,
+	pub mph: String,
+	pub knots: String,
+	pub ms: String,
+	pub bft: String,
+	pub hpa: String,
+	pub inhg: String,
+	pub mmhg: String,
+	pub kpa: String,
+	pub km: String,
+	pub mi: String,
+	// Abbreviations of durations, e.g., the day length `10h 05m`.
+	pub hours: String,
+	pub minutes: String,
+	pub seconds: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ErrorLocales {
+	pub address_required: String,
+	pub location_request_failed: String,
+	pub weather_request_failed: String,
+	pub historical_weather_request_failed: String,
+	pub day_out_of_range: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WeatherCodeLocales {
 	pub clear_sky: String,
@@ -87,8 +193,11 @@ impl Default for Locales {
 			greeting: "Hey friend. I'm glad you are asking.".to_string(),
 			search_station: "You didn't specify a city. Should I check for a weather station close to your location?"
 				.to_string(),
+			disclaimer: "Weather data by Open-Meteo.com".to_string(),
 			config: ConfigLocales::default(),
 			weather: WeatherLocales::default(),
+			units: UnitLocales::default(),
+			errors: ErrorLocales::default(),
 		}
 	}
 }
@@ -115,12 +224,234 @@ impl Default for WeatherLocales {
 			humidity: "Humidity".to_string(),
 			dew_point: "Dew Point".to_string(),
 			hourly_forecast: "Hourly Forecast".to_string(),
+			next_hours: PluralLocales {
+				one: "Next Hour".to_string(),
+				other: "Next {n} Hours".to_string(),
+			},
 			daily_overview: "Daily Overview".to_string(),
+			visibility: "Visibility".to_string(),
+			wind_direction: WindDirectionLocales::default(),
+			beaufort: BeaufortLocales::default(),
+			series: SeriesLocales::default(),
+			astronomy: AstronomyLocales::default(),
 			weather_code: WeatherCodeLocales::default(),
 		}
 	}
 }
 
+impl Default for WindDirectionLocales {
+	fn default() -> Self {
+		Self {
+			n: "N".to_string(),
+			ne: "NE".to_string(),
+			e: "E".to_string(),
+			se: "SE".to_string(),
+			s: "S".to_string(),
+			sw: "SW".to_string(),
+			w: "W".to_string(),
+			nw: "NW".to_string(),
+		}
+	}
+}
+
+impl Default for UnitLocales {
+	fn default() -> Self {
+		Self {
+			celsius: "°C".to_string(),
+			fahrenheit: "°F".to_string(),
+			kelvin: "K".to_string(),
+			mm: "mm".to_string(),
+			inch: "in".to_string(),
+			kmh: "km/h".to_string(),
+			mph: "mph".to_string(),
+			knots: "kn".to_string(),
+			ms: "m/s".to_string(),
+			bft: "Bft".to_string(),
+			hpa: "hPa".to_string(),
+			inhg: "inHg".to_string(),
+			mmhg: "mmHg".to_string(),
+			kpa: "kPa".to_string(),
+			km: "km".to_string(),
+			mi: "mi".to_string(),
+			hours: "h".to_string(),
+			minutes: "m".to_string(),
+			seconds: "s".to_string(),
+		}
+	}
+}
+
+// Languages that use the singular for 0 as well, see the CLDR plural rules.
+const SINGULAR_ZERO_LANGUAGES: [&str; 2] = ["fr", "pt"];
+
+impl PluralLocales {
+	// Picks the form for the count and the language, e.g.: `Next Hour` for 1, `Next 12 Hours` for 12.
+	pub fn select(&self, n: usize, lang: &str) -> String {
+		let short_lang_code = lang.split(['_', '-']).next().unwrap_or_default();
+		let singular_zero = SINGULAR_ZERO_LANGUAGES.contains(&short_lang_code) && lang != "pt_PT";
+		let form = if n == 1 || (n == 0 && singular_zero) { &self.one } else { &self.other };
+
+		form.replace("{n}", &n.to_string())
+	}
+}
+
+impl UnitLocales {
+	pub fn temperature(&self, unit: Temperature) -> &str {
+		match unit {
+			Temperature::celsius => &self.celsius,
+			Temperature::fahrenheit => &self.fahrenheit,
+			Temperature::kelvin => &self.kelvin,
+		}
+	}
+
+	// Amounts of precipitation are in millimeters if only the probability is requested.
+	pub fn precipitation(&self, unit: Precipitation) -> &str {
+		match unit {
+			Precipitation::probability | Precipitation::mm => &self.mm,
+			Precipitation::inch => &self.inch,
+		}
+	}
+
+	pub fn speed(&self, unit: Speed) -> &str {
+		match unit {
+			Speed::kmh => &self.kmh,
+			Speed::mph => &self.mph,
+			Speed::knots => &self.knots,
+			Speed::ms => &self.ms,
+			Speed::beaufort => &self.bft,
+		}
+	}
+
+	pub fn pressure(&self, unit: Pressure) -> &str {
+		match unit {
+			Pressure::hpa => &self.hpa,
+			Pressure::inhg => &self.inhg,
+			Pressure::mmhg => &self.mmhg,
+			Pressure::kpa => &self.kpa,
+		}
+	}
+
+	pub fn distance(&self, unit: Distance) -> &str {
+		match unit {
+			Distance::km => &self.km,
+			Distance::mi => &self.mi,
+		}
+	}
+}
+
+impl Default for BeaufortLocales {
+	fn default() -> Self {
+		Self {
+			calm: "Calm".to_string(),
+			light_air: "Light Air".to_string(),
+			light_breeze: "Light Breeze".to_string(),
+			gentle_breeze: "Gentle Breeze".to_string(),
+			moderate_breeze: "Moderate Breeze".to_string(),
+			fresh_breeze: "Fresh Breeze".to_string(),
+			strong_breeze: "Strong Breeze".to_string(),
+			near_gale: "Near Gale".to_string(),
+			gale: "Gale".to_string(),
+			strong_gale: "Strong Gale".to_string(),
+			storm: "Storm".to_string(),
+			violent_storm: "Violent Storm".to_string(),
+			hurricane: "Hurricane".to_string(),
+		}
+	}
+}
+
+impl BeaufortLocales {
+	pub fn describe(&self, force: u8) -> &str {
+		match force {
+			0 => &self.calm,
+			1 => &self.light_air,
+			2 => &self.light_breeze,
+			3 => &self.gentle_breeze,
+			4 => &self.moderate_breeze,
+			5 => &self.fresh_breeze,
+			6 => &self.strong_breeze,
+			7 => &self.near_gale,
+			8 => &self.gale,
+			9 => &self.strong_gale,
+			10 => &self.storm,
+			11 => &self.violent_storm,
+			_ => &self.hurricane,
+		}
+	}
+}
+
+impl Default for SeriesLocales {
+	fn default() -> Self {
+		Self {
+			temperature: "Temperature".to_string(),
+			precipitation: "Precipitation".to_string(),
+			wind: "Wind".to_string(),
+			humidity: "Humidity".to_string(),
+			pressure: "Pressure".to_string(),
+			uv_index: "UV Index".to_string(),
+		}
+	}
+}
+
+impl SeriesLocales {
+	pub fn name(&self, series: Series) -> &str {
+		match series {
+			Series::temperature => &self.temperature,
+			Series::precipitation => &self.precipitation,
+			Series::wind => &self.wind,
+			Series::humidity => &self.humidity,
+			Series::pressure => &self.pressure,
+			Series::uv_index => &self.uv_index,
+		}
+	}
+}
+
+impl Default for AstronomyLocales {
+	fn default() -> Self {
+		Self {
+			new_moon: "New Moon".to_string(),
+			waxing_crescent: "Waxing Crescent".to_string(),
+			first_quarter: "First Quarter".to_string(),
+			waxing_gibbous: "Waxing Gibbous".to_string(),
+			full_moon: "Full Moon".to_string(),
+			waning_gibbous: "Waning Gibbous".to_string(),
+			third_quarter: "Third Quarter".to_string(),
+			waning_crescent: "Waning Crescent".to_string(),
+			day_length: "Day Length".to_string(),
+			solar_noon: "Solar Noon".to_string(),
+			golden_hour: "Golden Hour".to_string(),
+			blue_hour: "Blue Hour".to_string(),
+		}
+	}
+}
+
+impl AstronomyLocales {
+	pub fn phase(&self, phase: Phase) -> &str {
+		match phase {
+			Phase::New => &self.new_moon,
+			Phase::WaxingCrescent => &self.waxing_crescent,
+			Phase::FirstQuarter => &self.first_quarter,
+			Phase::WaxingGibbous => &self.waxing_gibbous,
+			Phase::Full => &self.full_moon,
+			Phase::WaningGibbous => &self.waning_gibbous,
+			Phase::ThirdQuarter => &self.third_quarter,
+			Phase::WaningCrescent => &self.waning_crescent,
+		}
+	}
+}
+
+impl Default for ErrorLocales {
+	fn default() -> Self {
+		Self {
+			address_required: "Your configuration requires you to specify a city.".to_string(),
+			location_request_failed: "Location request failed.".to_string(),
+			weather_request_failed: "Weather data request failed.".to_string(),
+			historical_weather_request_failed: "Historical weather data request failed.".to_string(),
+			day_out_of_range:
+				"The requested day isn't covered by the forecast. It reaches 15 days ahead and 92 days back, use -F for earlier dates."
+					.to_string(),
+		}
+	}
+}
+
 impl Default for WeatherCodeLocales {
 	fn default() -> Self {
 		Self {
@@ -158,95 +489,106 @@ impl Default for WeatherCodeLocales {
 
 const DATETIME_LOCALES: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/pure-rust-locales.txt"));
 
-impl Locales {
-	pub async fn get(lang: &str) -> Result<Self> {
-		let mut texts = Self::default();
-		let path = Self::get_path(lang);
-
-		if let Ok(file) = fs::read_to_string(path) {
-			if let Ok(contents) = serde_json::from_str::<LocalesFile>(&file) {
-				contents.apply_to(&mut texts);
-				return Ok(texts);
-			};
-		};
+// Curated translations compiled into the binary. Files in the locales dir of the config take precedence.
+const BUNDLED_LOCALES: [(&str, &str); 6] = [
+	("de", include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/de.json"))),
+	("es", include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/es.json"))),
+	("fr", include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/fr.json"))),
+	("it", include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/it.json"))),
+	("nl", include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/nl.json"))),
+	("pt", include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/pt.json"))),
+];
 
-		if lang != "en_US" && lang != "en" {
-			texts.translate_all(lang).await?;
+impl Locales {
+	pub async fn get(lang: &str, translation: &Translation) -> Result<Self> {
+		let defaults = serde_json::to_value(Self::default())?;
+
+		// Texts of the locales dir take precedence over the bundled ones, and both can be partial.
+		let mut file = fs::read_to_string(Self::get_path(lang))
+			.ok()
+			.and_then(|file| serde_json::from_str::<Value>(&file).ok())
+			.unwrap_or_else(|| json!({}));
+		let mut texts = Self::get_bundled(lang).unwrap_or_else(|| json!({}));
+		merge(&mut texts, file.clone());
+
+		// Missing texts are only translated online if it's enabled in the config.
+		let missing = keys(&defaults)
+			.into_iter()
+			.filter(|key| texts.pointer(&pointer(key)).is_none())
+			.collect::<Vec<_>>();
+		if !missing.is_empty() && lang != "en_US" && lang != "en" && translation.is_enabled() {
+			let translated: Vec<_> = missing
+				.iter()
+				.map(|key| {
+					translation.translate(
+						lang,
+						defaults.pointer(&pointer(key)).and_then(Value::as_str).unwrap_or_default(),
+					)
+				})
+				.collect::<FuturesOrdered<_>>()
+				.try_collect()
+				.await?;
+
+			for (key, translated) in missing.iter().zip(translated) {
+				set(&mut file, key, Value::String(translated));
+			}
+			merge(&mut texts, file.clone());
+			Self::store(&file, lang);
 		}
 
-		Ok(texts)
+		let mut result = defaults;
+		merge(&mut result, texts);
+
+		Ok(serde_json::from_value(result)?)
 	}
 
-	#[allow(clippy::cast_ptr_alignment)]
-	async fn translate_all(&mut self, lang: &str) -> Result<()> {
-		let size = std::mem::size_of_val(self);
-		let ptr = (self as *mut Self).cast::<u8>();
+	// Compares the texts used for a language with the English ones. Returns their source,
+	// the keys missing from it and the keys that are unknown.
+	pub fn check(lang: &str) -> Result<(String, Vec<String>, Vec<String>)> {
+		let path = Self::get_path(lang);
+		let (source, texts) = match fs::read_to_string(&path) {
+			Ok(file) => (
+				path.display().to_string(),
+				serde_json::from_str::<Value>(&file).with_context(|| format!("Error parsing {}.", path.display()))?,
+			),
+			Err(_) => (
+				format!("the bundled texts of `{lang}`"),
+				Self::get_bundled(lang).ok_or_else(|| anyhow!("There are no texts for `{lang}`."))?,
+			),
+		};
 
-		// Iterate over each field in the struct, create a future to translate the current field's value
-		let translated_values: Vec<_> = (0..size)
-			.step_by(std::mem::size_of::<String>())
-			.map(|offset| unsafe {
-				let field_ptr = ptr.add(offset);
-				let field_value = &*(field_ptr.cast::<String>());
-				Self::translate_str(lang, field_value)
-			})
-			.collect::<FuturesOrdered<_>>()
-			// Wait for each future in the stream to complete and store the translated values in a vector
-			.try_collect()
-			.await?;
-
-		// Iterate over each field in the struct again, update current field value with the translated value
-		for (offset, translated_value) in (0..size).step_by(std::mem::size_of::<String>()).zip(translated_values) {
-			unsafe {
-				let field_ptr = ptr.add(offset);
-				field_ptr.cast::<String>().write(translated_value);
-			}
-		}
+		let (expected, found) = (keys(&serde_json::to_value(Self::default())?), keys(&texts));
+		let missing = expected.iter().filter(|key| !found.contains(key)).cloned().collect();
+		let extra = found.into_iter().filter(|key| !expected.contains(key)).collect();
 
-		Ok(())
+		Ok((source, missing, extra))
 	}
 
-	async fn translate_str(lang: &str, input: &str) -> Result<String> {
-		let url = Url::parse_with_params(
-			"https://translate.googleapis.com/translate_a/single?client=gtx&ie=UTF-8&oe=UTF-8&dt=t&sl=en_US",
-			&[("tl", lang), ("q", input)],
-		)?;
+	// Matches the full language code first, e.g., `pt_BR`, then its language part, e.g., `pt`.
+	fn get_bundled(lang: &str) -> Option<Value> {
+		let short_lang_code = lang.split(['_', '-']).next().unwrap_or_default();
+		let (_, file) = BUNDLED_LOCALES
+			.iter()
+			.find(|(code, _)| *code == lang)
+			.or_else(|| BUNDLED_LOCALES.iter().find(|(code, _)| *code == short_lang_code))?;
 
-		let res = reqwest::get(url)
-			.await?
-			.json::<Vec<Value>>()
-			.await
-			.with_context(|| "Translation request failed.")?;
-
-		let output = res.first().map_or_else(String::new, |i| {
-			i.as_array()
-				.unwrap()
-				.iter()
-				.map(|s| s[0].as_str().unwrap())
-				.collect::<Vec<&str>>()
-				.join("")
-		});
-
-		Ok(output)
+		serde_json::from_str(file).ok()
 	}
 
-	pub fn store(&self, lang: &str) {
+	fn store(texts: &Value, lang: &str) {
 		let path = Self::get_path(lang);
 		let dir = path.parent().unwrap();
 		if !dir.is_dir() {
-			fs::create_dir(dir).unwrap();
-		};
+			fs::create_dir_all(dir).unwrap();
+		}
 
 		let mut file = File::create(path).unwrap();
-		file.write_all(serde_json::to_string_pretty(self).unwrap().as_bytes()).unwrap();
+		file.write_all(serde_json::to_string_pretty(texts).unwrap().as_bytes()).unwrap();
 	}
 
+	// The locales directory sits next to the config file in use, which may be another one than the default.
 	pub fn get_path(lang: &str) -> PathBuf {
-		dirs::config_dir()
-			.unwrap()
-			.join(super::config::CONFIG_DIR_NAME)
-			.join("locales")
-			.join(format!("{lang}.json"))
+		Config::get_path().with_file_name("locales").join(format!("{lang}.json"))
 	}
 
 	#[allow(clippy::unnecessary_wraps)]
@@ -270,18 +612,128 @@ impl Locales {
 	}
 }
 
+// Lists the keys of all texts, with nested ones separated by dots, e.g.: `weather.humidity`.
+fn keys(value: &Value) -> Vec<String> {
+	match value {
+		Value::Object(map) => map
+			.iter()
+			.flat_map(|(name, value)| match value {
+				Value::Object(_) => keys(value).into_iter().map(|key| format!("{name}.{key}")).collect(),
+				_ => vec![name.clone()],
+			})
+			.collect(),
+		_ => vec![],
+	}
+}
+
+fn pointer(key: &str) -> String {
+	format!("/{}", key.replace('.', "/"))
+}
+
+fn set(target: &mut Value, key: &str, value: Value) {
+	let (parents, name) = key.rsplit_once('.').map_or(("", key), |(parents, name)| (parents, name));
+	let mut target = target;
+	for parent in parents.split('.').filter(|parent| !parent.is_empty()) {
+		if !target.get(parent).is_some_and(Value::is_object) {
+			target[parent] = json!({});
+		}
+		target = &mut target[parent];
+	}
+	target[name] = value;
+}
+
+fn merge(target: &mut Value, source: Value) {
+	match (target, source) {
+		(Value::Object(target), Value::Object(source)) => {
+			for (name, value) in source {
+				match target.get_mut(&name) {
+					Some(existing) if existing.is_object() && value.is_object() => merge(existing, value),
+					_ => {
+						target.insert(name, value);
+					}
+				}
+			}
+		}
+		(target, source) => *target = source,
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
-	#[tokio::test]
-	async fn translate_string() -> Result<()> {
-		let (target_lang, input) = ("de_DE", "tongue-twister");
+	#[test]
+	fn bundled_locales_are_complete() {
+		for (lang, file) in BUNDLED_LOCALES {
+			assert!(serde_json::from_str::<Locales>(file).is_ok(), "incomplete locale: {lang}");
+		}
 
-		let res = Locales::translate_str(target_lang, input).await?;
+		assert_eq!(
+			Locales::get_bundled("de_AT").and_then(|texts| texts["greeting"].as_str().map(str::to_string)),
+			Some("Hey Freund. Schön, dass du fragst.".to_string())
+		);
+		assert!(Locales::get_bundled("ja_JP").is_none());
+	}
+
+	#[test]
+	fn locales_next_to_config() {
+		let path = Locales::get_path("de_DE");
+
+		assert_eq!(path.file_name().unwrap(), "de_DE.json");
+		assert_eq!(path.parent(), Some(Config::get_path().with_file_name("locales").as_path()));
+	}
+
+	#[test]
+	fn bundled_texts() {
+		let bundled = |lang: &str| serde_json::from_value::<Locales>(Locales::get_bundled(lang).unwrap()).unwrap();
+		let (de, fr) = (bundled("de"), bundled("fr"));
+
+		assert_eq!(de.units.temperature(Temperature::fahrenheit), "°F");
+		assert_eq!(de.units.minutes, "min");
+		assert_eq!(fr.units.precipitation(Precipitation::inch), "po");
+		assert_eq!(fr.units.precipitation(Precipitation::probability), "mm");
+		assert_eq!(fr.units.speed(Speed::knots), "nd");
+		assert_eq!(de.errors.weather_request_failed, "Abfrage der Wetterdaten fehlgeschlagen.");
+	}
+
+	#[test]
+	fn plural_forms() {
+		let next_hours = Locales::default().weather.next_hours;
+		assert_eq!(next_hours.select(1, "en_US"), "Next Hour");
+		assert_eq!(next_hours.select(12, "en_US"), "Next 12 Hours");
+		assert_eq!(next_hours.select(0, "en_US"), "Next 0 Hours");
+
+		let next_hours = serde_json::from_value::<Locales>(Locales::get_bundled("fr").unwrap())
+			.unwrap()
+			.weather
+			.next_hours;
+		assert_eq!(next_hours.select(0, "fr_FR"), "Prochaine heure");
+		assert_eq!(next_hours.select(2, "fr_FR"), "2 prochaines heures");
+		assert_eq!(Locales::default().weather.next_hours.select(0, "pt_PT"), "Next 0 Hours");
+		assert_eq!(Locales::default().weather.next_hours.select(0, "pt_BR"), "Next Hour");
+	}
+
+	#[test]
+	fn merge_partial_texts() {
+		let mut texts = serde_json::to_value(Locales::default()).unwrap();
+		merge(
+			&mut texts,
+			json!({ "greeting": "Moin", "weather": { "weather_code": { "fog": "Nebel" } } }),
+		);
+		set(&mut texts, "config.confirm", json!("Ja"));
+		let texts = serde_json::from_value::<Locales>(texts).unwrap();
+
+		assert_eq!(texts.greeting, "Moin");
+		assert_eq!(texts.weather.weather_code.fog, "Nebel");
+		assert_eq!(texts.weather.weather_code.overcast, "Overcast");
+		assert_eq!(texts.config.confirm, "Ja");
+	}
 
-		assert!(res.contains("Zungenbrecher"));
+	#[test]
+	fn text_keys() {
+		let keys = keys(&json!({ "greeting": "Moin", "weather": { "humidity": "Luftfeuchtigkeit" } }));
 
-		Ok(())
+		assert_eq!(keys, ["greeting", "weather.humidity"]);
+		assert_eq!(pointer(&keys[1]), "/weather/humidity");
 	}
 }
@@ -4,7 +4,7 @@ use reqwest::Client;
 use serde::Deserialize;
 
 use super::{config::Config, localization::Locales};
-use crate::modules::api::{Api, ApiName, ApiQuery, ErrorMessage};
+use crate::modules::api::{Api, ApiName, ApiQuery};
 
 #[derive(Deserialize)]
 pub struct Location {
@@ -66,13 +66,15 @@ impl From<&OpenMeteoGeoObj> for Location {
 }
 
 impl Location {
-	pub async fn get(address: &str, lang: &str) -> Result<Self> {
+	pub async fn get(address: &str, lang: &str, texts: &Locales) -> Result<Self> {
 		let client = Client::builder().user_agent("wthrr-the-weathercrab").build()?;
 		let results = Self::search_osm(&client, address, lang).await;
 
 		match results {
 			Ok(address) => Ok(address),
-			Err(_) => Self::search_open_meteo(&client, address, lang).await,
+			Err(_) => Self::search_open_meteo(&client, address, lang)
+				.await
+				.map_err(|_| anyhow!(texts.errors.location_request_failed.clone())),
 		}
 	}
 
@@ -88,7 +90,7 @@ impl Location {
 			.json::<Vec<OpenStreetMapGeoObj>>()
 			.await?
 			.first()
-			.ok_or_else(|| anyhow!(Self::error_message()))
+			.ok_or_else(|| anyhow!("No location found."))
 			.map(Self::from)
 	}
 
@@ -100,14 +102,14 @@ impl Location {
 			.json::<Vec<OpenMeteoGeoObj>>()
 			.await?
 			.first()
-			.ok_or_else(|| anyhow!(Self::error_message()))
+			.ok_or_else(|| anyhow!("No location found."))
 			.map(Self::from)
 	}
 
 	pub async fn resolve_input(arg_address: &str, config: &Config, texts: &Locales) -> Result<String> {
 		if arg_address.is_empty() && config.address == "arg_input" {
-			bail!("Your configuration requires you to specify a city.");
-		};
+			bail!(texts.errors.address_required.clone());
+		}
 
 		let show_station_search_prompt = arg_address.is_empty() && config.address.is_empty();
 		if config.gui.greeting {
@@ -133,17 +135,11 @@ impl Location {
 		} else if !arg_address.is_empty() {
 			Ok(arg_address.to_string())
 		} else {
-			Ok(config.address.to_string())
+			Ok(config.address.clone())
 		}
 	}
 }
 
-impl ErrorMessage for Location {
-	fn error_message() -> String {
-		String::from("Location request failed.")
-	}
-}
-
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -182,8 +178,8 @@ mod tests {
 	async fn geolocation_response() -> Result<()> {
 		let (address, lang_de, lang_pl) = ("berlin", "de", "pl");
 
-		let loc_de = Location::get(address, lang_de).await?;
-		let loc_pl = Location::get(address, lang_pl).await?;
+		let loc_de = Location::get(address, lang_de, &Locales::default()).await?;
+		let loc_pl = Location::get(address, lang_pl, &Locales::default()).await?;
 
 		assert!(loc_de.name.contains("Deutschland"));
 		assert!(loc_pl.name.contains("Niemcy"));
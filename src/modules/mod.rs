@@ -1,10 +1,14 @@
 pub mod api;
 pub mod args;
+pub mod commands;
 pub mod config;
 pub mod display;
 pub mod forecast;
 pub mod localization;
 pub mod location;
+pub mod moon;
 pub mod params;
+pub mod sun;
+pub mod translation;
 pub mod units;
 pub mod weather;
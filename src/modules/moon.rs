@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+
+use super::sun::{self, days_since_j2000, horizontal, obliquity};
+
+// The eight phases of the moon, each covering an eighth of its orbit centered on the named position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+	New,
+	WaxingCrescent,
+	FirstQuarter,
+	WaxingGibbous,
+	Full,
+	WaningGibbous,
+	ThirdQuarter,
+	WaningCrescent,
+}
+
+pub struct Moon {
+	pub phase: Phase,
+	// Illuminated fraction of the disk from 0 to 1
+	pub illumination: f64,
+}
+
+impl Moon {
+	pub fn at(time: DateTime<Utc>) -> Self {
+		let (longitude, _, _) = ecliptic(days_since_j2000(time));
+		// Angle between the moon and the sun as seen from the earth, growing from new to full moon
+		let elongation = (longitude - sun::ecliptic_longitude(time)).rem_euclid(360.0);
+		let phase = match ((elongation + 22.5) / 45.0) as u8 % 8 {
+			0 => Phase::New,
+			1 => Phase::WaxingCrescent,
+			2 => Phase::FirstQuarter,
+			3 => Phase::WaxingGibbous,
+			4 => Phase::Full,
+			5 => Phase::WaningGibbous,
+			6 => Phase::ThirdQuarter,
+			_ => Phase::WaningCrescent,
+		};
+
+		Self {
+			phase,
+			illumination: (1.0 - elongation.to_radians().cos()) / 2.0,
+		}
+	}
+}
+
+// Elevation of the moon in degrees relative to the one at which its upper edge rises or sets. Includes the
+// refraction and the parallax of the moon, which is close enough to make it appear lower than from the earth's
+// center.
+pub fn elevation(lat: f64, lon: f64, time: DateTime<Utc>) -> f64 {
+	let n = days_since_j2000(time);
+	let (longitude, latitude, parallax) = ecliptic(n);
+	let (longitude, latitude, obliquity) = (longitude.to_radians(), latitude.to_radians(), obliquity(n));
+
+	let right_ascension = (longitude.sin() * obliquity.cos() - latitude.tan() * obliquity.sin()).atan2(longitude.cos());
+	let declination = (latitude.sin() * obliquity.cos() + latitude.cos() * obliquity.sin() * longitude.sin()).asin();
+
+	horizontal(lat, lon, n, right_ascension, declination).0 - (0.7275 * parallax - 0.5667)
+}
+
+// Ecliptic longitude and latitude and horizontal parallax of the moon in degrees. The low precision formulas of the
+// Astronomical Almanac are accurate to a few tenths of a degree.
+fn ecliptic(n: f64) -> (f64, f64, f64) {
+	// Julian centuries since J2000.0
+	let t = n / 36_525.0;
+	let sin = |degrees: f64| degrees.to_radians().sin();
+	let cos = |degrees: f64| degrees.to_radians().cos();
+
+	let longitude = 218.32 + 481_267.881 * t + 6.29 * sin(135.0 + 477_198.87 * t) - 1.27 * sin(259.3 - 413_335.36 * t)
+		+ 0.66 * sin(235.7 + 890_534.22 * t)
+		+ 0.21 * sin(269.9 + 954_397.74 * t)
+		- 0.19 * sin(357.5 + 35_999.05 * t)
+		- 0.11 * sin(186.5 + 966_404.03 * t);
+	let latitude = 5.13 * sin(93.3 + 483_202.02 * t) + 0.28 * sin(228.2 + 960_400.89 * t)
+		- 0.28 * sin(318.3 + 6_003.15 * t)
+		- 0.17 * sin(217.6 - 407_332.21 * t);
+	let parallax = 0.9508
+		+ 0.0518 * cos(135.0 + 477_198.87 * t)
+		+ 0.0095 * cos(259.3 - 413_335.36 * t)
+		+ 0.0078 * cos(235.7 + 890_534.22 * t)
+		+ 0.0028 * cos(269.9 + 954_397.74 * t);
+
+	(longitude.rem_euclid(360.0), latitude, parallax)
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::TimeZone;
+
+	use super::*;
+
+	fn utc(m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(2024, m, d, h, min, 0).unwrap()
+	}
+
+	#[test]
+	fn phases() {
+		// New moon on the 2nd, first quarter on the 10th and full moon on the 17th of October 2024
+		let new = Moon::at(utc(10, 2, 18, 49));
+		assert_eq!(new.phase, Phase::New);
+		assert!(new.illumination < 0.01);
+
+		let first_quarter = Moon::at(utc(10, 10, 18, 55));
+		assert_eq!(first_quarter.phase, Phase::FirstQuarter);
+		assert!((first_quarter.illumination - 0.5).abs() < 0.02);
+
+		let full = Moon::at(utc(10, 17, 11, 26));
+		assert_eq!(full.phase, Phase::Full);
+		assert!(full.illumination > 0.99);
+
+		assert_eq!(Moon::at(utc(10, 7, 11, 0)).phase, Phase::WaxingCrescent);
+		assert_eq!(Moon::at(utc(10, 27, 11, 0)).phase, Phase::WaningCrescent);
+	}
+
+	#[test]
+	fn moon_elevation() {
+		// The waxing crescent rose in Berlin in the morning of the 7th of October 2024 and set after the sun.
+		let elevation = |h| elevation(52.52, 13.41, utc(10, 7, h, 0));
+		assert!(elevation(6) < 0.0 && elevation(12) > 0.0 && elevation(15) > 0.0);
+		assert!(elevation(20) < 0.0);
+	}
+}
@@ -1,12 +1,17 @@
 use std::collections::HashSet;
 
 use anyhow::{Context, Result};
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate};
 use dialoguer::{theme::ColorfulTheme, Confirm, Select};
 
 use super::{
-	args::{Cli, Forecast},
+	args::{Cli, Day, Forecast, ForecastArg, Output},
 	config::Config,
+	display::{
+		hourly::{LabelStep, Layout, Window},
+		icons::IconSet,
+	},
+	forecast::{self, MAX_FORECAST_DAYS, MAX_PAST_DAYS},
 	localization::{ConfigLocales, Locales},
 	location::Location,
 	units::Units,
@@ -15,37 +20,56 @@ use super::{
 #[derive(Debug, Clone)]
 pub struct Params {
 	pub config: Config,
+	// The config file's own values with the explicit arguments, which are saved without the layers of the profile
+	// and the environment variables.
+	pub stored_config: Config,
 	pub texts: Locales,
 	pub historical_weather: HashSet<NaiveDate>,
+	// Days of the forecast that are requested by their distance from today or their date.
+	pub days: Vec<Day>,
+	pub output: Option<Output>,
+	pub icons: bool,
+	pub layout: Layout,
 }
 
 impl Params {
-	pub async fn merge(config: &Config, args: &Cli) -> Result<Self> {
+	// Precedence from highest to lowest: arguments, environment variables, profile, config file, defaults.
+	pub async fn merge(config_file: &Config, args: &Cli) -> Result<Self> {
+		let config = &config_file.clone().apply_profile(args.profile.as_deref())?.merge_env()?;
+
 		let language = match &args.language {
-			Some(lang) => lang.to_string(),
+			Some(lang) => lang.clone(),
 			None => config.language.clone(),
 		};
 
-		let texts = Locales::get(&language).await?;
+		let texts = Locales::get(&language, &config.translation).await?;
 
 		if args.reset {
 			Self::reset(&texts.config)?;
 			std::process::exit(1);
 		}
 
-		let units = Units::merge(&args.units, config.units);
+		let units = Units::merge(
+			&args.units,
+			config.clone().localize_units(args.profile.as_deref(), &language).units,
+		);
 
-		let address = Location::resolve_input(args.address.as_deref().unwrap_or_default(), config, &texts).await?;
+		// Keep the greeting out of output that is meant to be redirected into a file.
+		let mut input_config = config.clone();
+		input_config.gui.greeting &= args.output.is_none();
+		let address =
+			Location::resolve_input(args.address.as_deref().unwrap_or_default(), &input_config, &texts).await?;
 
-		let forecast = if args.forecast.contains(&Forecast::disable)
-			|| (args.forecast.is_empty() && !args.historical_weather.is_empty())
-		{
-			HashSet::<Forecast>::new()
-		} else if !args.forecast.is_empty() {
-			args.forecast.iter().copied().collect()
-		} else {
-			config.forecast.clone()
-		};
+		let disabled = args.forecast.contains(&ForecastArg::Named(Forecast::disable));
+		let forecast = Self::forecast(&config.forecast, args);
+		let days = args
+			.forecast
+			.iter()
+			.filter_map(|arg| match arg {
+				ForecastArg::Day(day) if !disabled => Some(*day),
+				_ => None,
+			})
+			.collect();
 
 		let historical_weather = if args.historical_weather.is_empty() {
 			HashSet::<NaiveDate>::new()
@@ -53,21 +77,119 @@ impl Params {
 			args.historical_weather.iter().copied().collect()
 		};
 
-		let gui = config.gui.clone();
+		let mut gui = config.gui.clone();
+		if let Some(hours) = args.next {
+			gui.hourly.window = Window::next(hours);
+		}
+		if let Some(hours) = args.label_step {
+			gui.hourly.label_step = LabelStep::hours(hours);
+		}
+		// Emoji and Unicode symbols render without a Nerd Font, so other formats keep them anyway.
+		let icons = args.icons || matches!(gui.icons, IconSet::emoji | IconSet::unicode);
 
 		Ok(Self {
+			stored_config: Self::stored_config(config_file, args, &address),
 			config: Config {
+				version: config.version,
 				address,
 				language,
+				translation: config.translation.clone(),
 				forecast,
+				forecast_days: config.forecast_days,
+				past_days: config.past_days,
 				units,
 				gui,
+				profiles: config.profiles.clone(),
 			},
 			texts,
 			historical_weather,
+			days,
+			output: args.output,
+			icons,
+			layout: Layout::detect(args.width),
 		})
 	}
 
+	fn forecast(configured: &HashSet<Forecast>, args: &Cli) -> HashSet<Forecast> {
+		let disabled = args.forecast.contains(&ForecastArg::Named(Forecast::disable));
+		let mut forecast = if disabled || (args.forecast.is_empty() && !args.historical_weather.is_empty()) {
+			HashSet::<Forecast>::new()
+		} else if !args.forecast.is_empty() {
+			args.forecast
+				.iter()
+				.filter_map(|arg| match arg {
+					ForecastArg::Named(forecast) => Some(*forecast),
+					ForecastArg::Day(_) => None,
+				})
+				.collect()
+		} else {
+			configured.clone()
+		};
+		// Asking for the next hours implies the hourly forecast of today, whatever else is shown.
+		if args.next.is_some() && !disabled {
+			forecast.insert(Forecast::day);
+		}
+
+		forecast
+	}
+
+	fn stored_config(config_file: &Config, args: &Cli, address: &str) -> Config {
+		let mut config = config_file.clone();
+		config.address = address.to_string();
+		if let Some(language) = &args.language {
+			config.language.clone_from(language);
+		}
+		let forecast = args
+			.forecast
+			.iter()
+			.filter_map(|arg| match arg {
+				ForecastArg::Named(forecast) => Some(*forecast),
+				ForecastArg::Day(_) => None,
+			})
+			.collect::<HashSet<_>>();
+		if !forecast.is_empty() {
+			config.forecast = forecast;
+		}
+		config.units = Units::merge(&args.units, config.units);
+		if let Some(hours) = args.next {
+			config.gui.hourly.window = Window::next(hours);
+		}
+		if let Some(hours) = args.label_step {
+			config.gui.hourly.label_step = LabelStep::hours(hours);
+		}
+
+		config
+	}
+
+	// Days before and after today to request: the configured ones, extended to reach the requested days.
+	pub fn forecast_range(&self) -> (u8, u8) {
+		self.forecast_range_from(Local::now().date_naive())
+	}
+
+	// The location's date is still unknown and may be a day before or after `today`, so the range covers the
+	// requested days counted from either of them.
+	fn forecast_range_from(&self, today: NaiveDate) -> (u8, u8) {
+		let offsets = [today.pred_opt(), Some(today), today.succ_opt()]
+			.into_iter()
+			.flatten()
+			.flat_map(|date| forecast::get_offsets(&self.config.forecast, &self.days, date))
+			.collect::<Vec<_>>();
+		let (first, last) = (
+			offsets.iter().min().copied().unwrap_or_default(),
+			offsets.iter().max().copied().unwrap_or_default(),
+		);
+		(
+			u8::try_from(-first)
+				.unwrap_or_default()
+				.max(self.config.past_days)
+				.min(MAX_PAST_DAYS),
+			u8::try_from(last + 1)
+				.unwrap_or_default()
+				.max(self.config.forecast_days)
+				.clamp(1, MAX_FORECAST_DAYS),
+		)
+	}
+
 	pub fn handle_next(self, args: Cli, config_file: &Config) -> Result<()> {
 		if !args.save && !config_file.address.is_empty() {
 			return Ok(());
@@ -78,8 +200,7 @@ impl Params {
 			self.save_prompt(&args.address.unwrap_or_default())?;
 		} else {
 			// Handle explicit save call
-			self.config.store().context("Error saving config file.")?;
-			self.texts.store(&config_file.language);
+			self.stored_config.store().context("Error saving config file.")?;
 		}
 
 		Ok(())
@@ -105,13 +226,12 @@ impl Params {
 		match selection {
 			0 => {}
 			1 => return Ok(()),
-			2 => self.config.address = "arg_input".to_string(),
-			3 => self.config.address = "auto".to_string(),
+			2 => self.stored_config.address = "arg_input".to_string(),
+			3 => self.stored_config.address = "auto".to_string(),
 			_ => println!("{}", self.texts.config.no_selection),
 		}
 
-		self.config.store().context("Error saving config file.")?;
-		self.texts.store(&self.config.language);
+		self.stored_config.store().context("Error saving config file.")?;
 
 		Ok(())
 	}
@@ -122,11 +242,93 @@ impl Params {
 			.interact()?;
 
 		if confirmation {
+			// A config file given by `--config` might share its directory with unrelated files.
 			let path = Config::get_path();
+			if path != Config::get_default_path() && path.is_file() {
+				std::fs::remove_file(path).with_context(|| "Error resetting config file.")?;
+			}
 
-			std::fs::remove_dir_all(path.parent().unwrap()).with_context(|| "Error resetting config file.")?;
+			let dir = Config::get_default_path();
+			let dir = dir.parent().unwrap();
+			if dir.is_dir() {
+				std::fs::remove_dir_all(dir).with_context(|| "Error resetting config file.")?;
+			}
 		}
 
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use clap::Parser;
+
+	use super::*;
+	use crate::modules::{
+		config::ConfigFile,
+		display::gui_config::{ColorVariant, ConfigFileGui},
+		units::Temperature,
+	};
+
+	#[test]
+	fn stored_config_leaves_out_the_profile() {
+		let mut config_file = Config::default();
+		config_file.profiles.insert(
+			"ssh".to_string(),
+			ConfigFile {
+				gui: Some(ConfigFileGui {
+					color: Some(ColorVariant::plain),
+					..ConfigFileGui::default()
+				}),
+				..ConfigFile::default()
+			},
+		);
+		let args = Cli::try_parse_from(["wthrr", "--profile", "ssh", "-u", "f", "-l", "de", "-f", "w"]).unwrap();
+
+		let stored = Params::stored_config(&config_file, &args, "Berlin");
+		assert_eq!(stored.gui.color, ColorVariant::default);
+		assert_eq!(stored.units.temperature, Temperature::fahrenheit);
+		assert_eq!((stored.address.as_str(), stored.language.as_str()), ("Berlin", "de"));
+		assert_eq!(stored.forecast, HashSet::from([Forecast::week]));
+		assert_eq!(stored.profiles, config_file.profiles);
+	}
+
+	#[test]
+	fn forecast_range_for_a_location_a_day_ahead() {
+		let today = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+		let mut params = Params {
+			config: Config::default(),
+			stored_config: Config::default(),
+			texts: Locales::default(),
+			historical_weather: HashSet::new(),
+			days: vec![],
+			output: None,
+			icons: false,
+			layout: Layout::default(),
+		};
+
+		// Two days ago on the machine are three days ago where it's already tomorrow.
+		params.days = vec![Day::Date(NaiveDate::from_ymd_opt(2026, 10, 17).unwrap())];
+		assert_eq!(params.forecast_range_from(today).0, 3);
+
+		params.days = vec![Day::Date(NaiveDate::from_ymd_opt(2026, 10, 25).unwrap())];
+		assert_eq!(params.forecast_range_from(today).1, 8);
+
+		params.days = vec![Day::Relative(15)];
+		assert_eq!(params.forecast_range_from(today), (0, 16));
+	}
+
+	#[test]
+	fn next_hours_show_today() {
+		let configured = HashSet::from([Forecast::week]);
+		let forecast = |args: &[&str]| Params::forecast(&configured, &Cli::try_parse_from(args).unwrap());
+
+		assert_eq!(forecast(&["wthrr"]), HashSet::from([Forecast::week]));
+		assert_eq!(forecast(&["wthrr", "-n", "6"]), HashSet::from([Forecast::week, Forecast::day]));
+		assert_eq!(
+			forecast(&["wthrr", "-n", "6", "-f", "tomorrow"]),
+			HashSet::from([Forecast::tomorrow, Forecast::day])
+		);
+		assert_eq!(forecast(&["wthrr", "-n", "6", "-f", "disable"]), HashSet::new());
+	}
+}
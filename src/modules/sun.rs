@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+
+// Elevations of the sun's center below which a day ends and a twilight turns into the next one, in degrees.
+// Sunrise and sunset are taken when the upper edge touches the horizon, including refraction.
+pub const SUNRISE: f64 = -0.833;
+const CIVIL_TWILIGHT: f64 = -6.0;
+const NAUTICAL_TWILIGHT: f64 = -12.0;
+
+// How far the sun is up at a place and time, which decides the icons of day and night.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Daylight {
+	Day,
+	// The sun is just below the horizon and rising in the morning or setting in the evening.
+	CivilTwilight { rising: bool },
+	NauticalTwilight,
+	Night,
+}
+
+impl Daylight {
+	// Also covers polar day and polar night, when the sun doesn't rise or set at all.
+	pub fn at(lat: f64, lon: f64, time: DateTime<Utc>) -> Self {
+		let (elevation, hour_angle) = position(lat, lon, time);
+		match elevation {
+			e if e >= SUNRISE => Self::Day,
+			e if e >= CIVIL_TWILIGHT => Self::CivilTwilight { rising: hour_angle < 0.0 },
+			e if e >= NAUTICAL_TWILIGHT => Self::NauticalTwilight,
+			_ => Self::Night,
+		}
+	}
+
+	pub const fn is_night(self) -> bool {
+		matches!(self, Self::NauticalTwilight | Self::Night)
+	}
+}
+
+// Elevation of the sun above the horizon and its hour angle, negative before and positive after noon, in
+// degrees. Based on the low precision formulas of the Astronomical Almanac, which are accurate to about a minute
+// of the sunrise and sunset times.
+pub fn position(lat: f64, lon: f64, time: DateTime<Utc>) -> (f64, f64) {
+	let n = days_since_j2000(time);
+	let (ecliptic_longitude, obliquity) = (ecliptic_longitude(time).to_radians(), obliquity(n));
+
+	let right_ascension = (obliquity.cos() * ecliptic_longitude.sin()).atan2(ecliptic_longitude.cos());
+	let declination = (obliquity.sin() * ecliptic_longitude.sin()).asin();
+
+	horizontal(lat, lon, n, right_ascension, declination)
+}
+
+// Ecliptic longitude of the sun in degrees, as needed for the phase of the moon.
+pub fn ecliptic_longitude(time: DateTime<Utc>) -> f64 {
+	let n = days_since_j2000(time);
+	let mean_anomaly = (357.528 + 0.985_600_3 * n).to_radians();
+	280.460 + 0.985_647_4 * n + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin()
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn days_since_j2000(time: DateTime<Utc>) -> f64 {
+	time.timestamp() as f64 / 86_400.0 - 10_957.5
+}
+
+// Obliquity of the ecliptic in radians.
+pub fn obliquity(n: f64) -> f64 {
+	(23.439 - 0.000_000_4 * n).to_radians()
+}
+
+// Elevation and hour angle in degrees of a body at equatorial coordinates given in radians.
+pub fn horizontal(lat: f64, lon: f64, n: f64, right_ascension: f64, declination: f64) -> (f64, f64) {
+	let sidereal_time = (280.460_618_37 + 360.985_647_366_29 * n + lon).to_radians();
+	let hour_angle = (sidereal_time - right_ascension).to_degrees().rem_euclid(360.0);
+	let hour_angle = if hour_angle > 180.0 { hour_angle - 360.0 } else { hour_angle };
+
+	let lat = lat.to_radians();
+	let elevation = (lat.sin() * declination.sin() + lat.cos() * declination.cos() * hour_angle.to_radians().cos())
+		.clamp(-1.0, 1.0)
+		.asin()
+		.to_degrees();
+
+	(elevation, hour_angle)
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::TimeZone;
+
+	use super::*;
+
+	const BERLIN: (f64, f64) = (52.52, 13.41);
+	const TROMSO: (f64, f64) = (69.65, 18.96);
+
+	fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+	}
+
+	#[test]
+	fn sun_position() {
+		// Solar noon in Berlin on the 7th of October 2024 was at 12:54 CEST.
+		let (elevation, hour_angle) = position(BERLIN.0, BERLIN.1, utc(2024, 10, 7, 10, 54));
+		assert!((elevation - 31.7).abs() < 0.1, "{elevation}");
+		assert!(hour_angle.abs() < 0.5, "{hour_angle}");
+	}
+
+	#[test]
+	fn minute_accurate_daylight() {
+		// Sunrise in Berlin on the 7th of October 2024 was at 7:19 and sunset at 18:29 CEST.
+		let daylight = |h, min| Daylight::at(BERLIN.0, BERLIN.1, utc(2024, 10, 7, h, min));
+		assert_eq!(daylight(5, 17), Daylight::CivilTwilight { rising: true });
+		assert_eq!(daylight(5, 21), Daylight::Day);
+		assert_eq!(daylight(16, 27), Daylight::Day);
+		assert_eq!(daylight(16, 31), Daylight::CivilTwilight { rising: false });
+		assert_eq!(daylight(17, 20), Daylight::NauticalTwilight);
+		assert_eq!(daylight(18, 0), Daylight::Night);
+		assert!(daylight(17, 20).is_night() && !daylight(16, 31).is_night());
+	}
+
+	#[test]
+	fn polar_day_and_night() {
+		// The sun neither sets at midsummer nor rises at midwinter in Tromsø.
+		let daylight = |m, d, h| Daylight::at(TROMSO.0, TROMSO.1, utc(2024, m, d, h, 0));
+		assert!((0..24).all(|h| daylight(6, 21, h) == Daylight::Day));
+		assert!((0..24).all(|h| daylight(12, 21, h) != Daylight::Day));
+		assert!(matches!(daylight(12, 21, 11), Daylight::CivilTwilight { .. }));
+	}
+}
@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Service used to translate texts of languages that have neither a bundled nor a stored translation.
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[allow(non_camel_case_types)]
+pub enum Translation {
+	#[default]
+	none,
+	google,
+	libretranslate {
+		url: String,
+		key: Option<String>,
+	},
+	deepl {
+		key: String,
+		url: Option<String>,
+	},
+}
+
+#[derive(Deserialize)]
+struct LibreTranslateResponse {
+	#[serde(rename = "translatedText")]
+	translated_text: String,
+}
+
+#[derive(Deserialize)]
+struct DeepLResponse {
+	translations: Vec<DeepLTranslation>,
+}
+
+#[derive(Deserialize)]
+struct DeepLTranslation {
+	text: String,
+}
+
+impl Translation {
+	pub const fn is_enabled(&self) -> bool {
+		!matches!(self, Self::none)
+	}
+
+	// Placeholders like `{n}` are sent as numbers, which the services neither translate nor drop as easily, and
+	// which let them pick a fitting plural. The English text is kept if a placeholder gets lost anyway.
+	pub async fn translate(&self, lang: &str, input: &str) -> Result<String> {
+		let placeholders = placeholders(input);
+		let protected = placeholders
+			.iter()
+			.enumerate()
+			.fold(input.to_string(), |text, (i, placeholder)| text.replace(placeholder, &token(i)));
+
+		let translated = match self {
+			Self::none => return Ok(input.to_string()),
+			Self::google => Self::translate_google(lang, &protected).await?,
+			Self::libretranslate { url, key } => Self::translate_libre(url, key.as_deref(), lang, &protected).await?,
+			Self::deepl { key, url } => Self::translate_deepl(key, url.as_deref(), lang, &protected).await?,
+		};
+
+		Ok(restore(&translated, &placeholders).unwrap_or_else(|| input.to_string()))
+	}
+
+	async fn translate_google(lang: &str, input: &str) -> Result<String> {
+		let url = Url::parse_with_params(
+			"https://translate.googleapis.com/translate_a/single?client=gtx&ie=UTF-8&oe=UTF-8&dt=t&sl=en_US",
+			&[("tl", lang), ("q", input)],
+		)?;
+
+		let res = reqwest::get(url)
+			.await?
+			.json::<Vec<Value>>()
+			.await
+			.with_context(|| "Translation request failed.")?;
+
+		let output = res.first().map_or_else(String::new, |i| {
+			i.as_array()
+				.unwrap()
+				.iter()
+				.map(|s| s[0].as_str().unwrap())
+				.collect::<Vec<&str>>()
+				.join("")
+		});
+
+		Ok(output)
+	}
+
+	async fn translate_libre(url: &str, key: Option<&str>, lang: &str, input: &str) -> Result<String> {
+		let res = Client::new()
+			.post(format!("{}/translate", url.trim_end_matches('/')))
+			.json(&json!({
+				"q": input,
+				"source": "en",
+				"target": Self::short_lang_code(lang),
+				"format": "text",
+				"api_key": key.unwrap_or_default(),
+			}))
+			.send()
+			.await?
+			.error_for_status()?
+			.json::<LibreTranslateResponse>()
+			.await
+			.with_context(|| "Translation request failed.")?;
+
+		Ok(res.translated_text)
+	}
+
+	async fn translate_deepl(key: &str, url: Option<&str>, lang: &str, input: &str) -> Result<String> {
+		// Keys of the free API are suffixed with `:fx` and only valid for its own endpoint.
+		let url = url.unwrap_or(if key.ends_with(":fx") {
+			"https://api-free.deepl.com/v2/translate"
+		} else {
+			"https://api.deepl.com/v2/translate"
+		});
+
+		let res = Client::new()
+			.post(url)
+			.header("Authorization", format!("DeepL-Auth-Key {key}"))
+			.json(&json!({
+				"text": [input],
+				"source_lang": "EN",
+				"target_lang": Self::deepl_lang_code(lang),
+			}))
+			.send()
+			.await?
+			.error_for_status()?
+			.json::<DeepLResponse>()
+			.await
+			.with_context(|| "Translation request failed.")?;
+
+		Ok(res.translations.into_iter().map(|t| t.text).collect())
+	}
+
+	fn short_lang_code(lang: &str) -> String {
+		lang.split(['_', '-']).next().unwrap_or_default().to_lowercase()
+	}
+
+	// DeepL only distinguishes regional variants for English and Portuguese, e.g.: `PT-BR`.
+	fn deepl_lang_code(lang: &str) -> String {
+		let code = lang.replace('_', "-").to_uppercase();
+		if ["EN-GB", "EN-US", "PT-BR", "PT-PT"].contains(&code.as_str()) {
+			code
+		} else {
+			Self::short_lang_code(lang).to_uppercase()
+		}
+	}
+}
+
+// Placeholders of a text, e.g.: `["{n}"]` for `Next {n} Hours`.
+fn placeholders(input: &str) -> Vec<&str> {
+	let mut placeholders = vec![];
+	let mut rest = input;
+	while let Some(start) = rest.find('{') {
+		let Some(end) = rest[start..].find('}') else { break };
+		let placeholder = &rest[start..=start + end];
+		if !placeholders.contains(&placeholder) {
+			placeholders.push(placeholder);
+		}
+		rest = &rest[start + end + 1..];
+	}
+	placeholders
+}
+
+// Two-digit numbers go without digit grouping in every language.
+fn token(i: usize) -> String {
+	(99 - i).to_string()
+}
+
+// Swaps the tokens back, unless one of them didn't make it through the translation unchanged.
+fn restore(translated: &str, placeholders: &[&str]) -> Option<String> {
+	placeholders
+		.iter()
+		.enumerate()
+		.try_fold(translated.to_string(), |text, (i, placeholder)| {
+			(text.matches(&token(i)).count() == 1).then(|| text.replace(&token(i), placeholder))
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn translate_string() -> Result<()> {
+		let (target_lang, input) = ("de_DE", "tongue-twister");
+
+		let res = Translation::google.translate(target_lang, input).await?;
+
+		assert!(res.contains("Zungenbrecher"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn protected_placeholders() {
+		assert_eq!(placeholders("Next {n} Hours"), ["{n}"]);
+		assert!(placeholders("Next Hour").is_empty());
+		assert_eq!(token(0), "99");
+
+		assert_eq!(restore("Nächste 99 Stunden", &["{n}"]).as_deref(), Some("Nächste {n} Stunden"));
+		assert_eq!(restore("Nächste Stunden", &["{n}"]), None);
+		assert_eq!(restore("Nächste Stunde", &[]).as_deref(), Some("Nächste Stunde"));
+	}
+
+	#[test]
+	fn lang_codes() {
+		assert_eq!(Translation::short_lang_code("de_DE"), "de");
+		assert_eq!(Translation::deepl_lang_code("de_DE"), "DE");
+		assert_eq!(Translation::deepl_lang_code("pt_BR"), "PT-BR");
+		assert_eq!(Translation::deepl_lang_code("en"), "EN");
+	}
+
+	#[test]
+	fn config_values() -> Result<()> {
+		let translation = ron::from_str::<Translation>("libretranslate(url: \"http://localhost:5000\")")?;
+
+		assert_eq!(
+			translation,
+			Translation::libretranslate {
+				url: "http://localhost:5000".to_string(),
+				key: None
+			}
+		);
+		assert!(!Translation::none.is_enabled());
+
+		Ok(())
+	}
+}
@@ -9,10 +9,18 @@ use super::args::UnitArg;
 #[optional_struct(ConfigFileUnits)]
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct Units {
+	#[optional_serde_skip_none]
 	pub temperature: Temperature,
+	#[optional_serde_skip_none]
 	pub speed: Speed,
+	#[optional_serde_skip_none]
 	pub time: Time,
+	#[optional_serde_skip_none]
 	pub precipitation: Precipitation,
+	#[optional_serde_skip_none]
+	pub pressure: Pressure,
+	#[optional_serde_skip_none]
+	pub distance: Distance,
 }
 
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, AsRefStr, VariantNames, EnumString)]
@@ -21,6 +29,7 @@ pub enum Temperature {
 	#[default]
 	celsius,
 	fahrenheit,
+	kelvin,
 }
 
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, AsRefStr, VariantNames, EnumString)]
@@ -31,6 +40,7 @@ pub enum Speed {
 	mph,
 	knots,
 	ms,
+	beaufort,
 }
 
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, AsRefStr, VariantNames, EnumString)]
@@ -50,7 +60,127 @@ pub enum Precipitation {
 	inch,
 }
 
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, AsRefStr, VariantNames, EnumString)]
+#[allow(non_camel_case_types)]
+pub enum Pressure {
+	#[default]
+	hpa,
+	inhg,
+	mmhg,
+	kpa,
+}
+
+// Units of the visibility.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, AsRefStr, VariantNames, EnumString)]
+#[allow(non_camel_case_types)]
+pub enum Distance {
+	#[default]
+	km,
+	mi,
+}
+
+// Upper bounds of the Beaufort scale in km/h, everything above is a hurricane (12).
+const BEAUFORT_LIMITS: [f32; 12] = [1.0, 6.0, 12.0, 20.0, 29.0, 39.0, 50.0, 62.0, 75.0, 89.0, 103.0, 118.0];
+
+// Open-Meteo doesn't provide Kelvin and the Beaufort scale. They are requested as Celsius and km/h and converted.
+impl Temperature {
+	pub const fn api_name(self) -> &'static str {
+		match self {
+			Self::celsius | Self::kelvin => "celsius",
+			Self::fahrenheit => "fahrenheit",
+		}
+	}
+
+	pub fn convert_api_value(self, value: f32) -> f32 {
+		match self {
+			Self::kelvin => value + 273.15,
+			_ => value,
+		}
+	}
+
+	pub fn to_celsius(self, value: f32) -> f32 {
+		match self {
+			Self::celsius => value,
+			Self::fahrenheit => (value - 32.0) * 5.0 / 9.0,
+			Self::kelvin => value - 273.15,
+		}
+	}
+}
+
+impl Speed {
+	pub const fn api_name(self) -> &'static str {
+		match self {
+			Self::kmh | Self::beaufort => "kmh",
+			Self::mph => "mph",
+			Self::knots => "kn",
+			Self::ms => "ms",
+		}
+	}
+
+	pub fn convert_api_value(self, value: f32) -> f32 {
+		match self {
+			#[allow(clippy::cast_precision_loss)]
+			Self::beaufort => BEAUFORT_LIMITS.iter().take_while(|limit| value >= **limit).count() as f32,
+			_ => value,
+		}
+	}
+}
+
+// Pressure is always provided in hPa.
+impl Pressure {
+	pub fn convert_api_value(self, hpa: f32) -> f32 {
+		match self {
+			Self::hpa => hpa,
+			Self::inhg => (hpa * 0.029_53 * 100.0).round() / 100.0,
+			Self::mmhg => (hpa * 0.750_062).round(),
+			Self::kpa => (hpa / 10.0 * 100.0).round() / 100.0,
+		}
+	}
+}
+
+// Visibility is always provided in meters.
+impl Distance {
+	pub fn convert_api_value(self, meters: f32) -> f32 {
+		match self {
+			Self::km => meters / 1000.0,
+			Self::mi => meters / 1609.344,
+		}
+	}
+}
+
+// Regions that deviate from metric units and the 24-hour clock.
+const FAHRENHEIT_REGIONS: [&str; 6] = ["US", "BS", "BZ", "KY", "LR", "PW"];
+const MPH_REGIONS: [&str; 4] = ["US", "GB", "LR", "MM"];
+const AM_PM_REGIONS: [&str; 10] = ["US", "AU", "BD", "CA", "EG", "IN", "NZ", "PH", "PK", "SA"];
+const INCH_REGIONS: [&str; 3] = ["US", "LR", "MM"];
+const INHG_REGIONS: [&str; 1] = ["US"];
+const MILE_REGIONS: [&str; 4] = ["US", "GB", "LR", "MM"];
+
 impl Units {
+	// Defaults for the region of a language code, e.g.: `en_US` uses fahrenheit, mph, inch, inHg, miles and the 12-hour clock.
+	// Languages without a region get the metric units and the 24-hour clock.
+	pub fn for_language(lang: &str) -> Self {
+		let region = lang.split(['_', '-']).nth(1).unwrap_or_default().to_uppercase();
+		let region = region.as_str();
+
+		Self {
+			temperature: if FAHRENHEIT_REGIONS.contains(&region) {
+				Temperature::fahrenheit
+			} else {
+				Temperature::celsius
+			},
+			speed: if MPH_REGIONS.contains(&region) { Speed::mph } else { Speed::kmh },
+			time: if AM_PM_REGIONS.contains(&region) { Time::am_pm } else { Time::military },
+			precipitation: if INCH_REGIONS.contains(&region) {
+				Precipitation::inch
+			} else {
+				Precipitation::probability
+			},
+			pressure: if INHG_REGIONS.contains(&region) { Pressure::inhg } else { Pressure::hpa },
+			distance: if MILE_REGIONS.contains(&region) { Distance::mi } else { Distance::km },
+		}
+	}
+
 	pub fn merge(arg_units: &[UnitArg], cfg_units: Self) -> Self {
 		cfg_units.assign_unit_args(arg_units)
 	}
@@ -69,6 +199,12 @@ impl Units {
 			if Precipitation::VARIANTS.as_ref().contains(&val.as_ref()) {
 				self.precipitation = Precipitation::from_str(val.as_ref()).unwrap();
 			}
+			if Pressure::VARIANTS.as_ref().contains(&val.as_ref()) {
+				self.pressure = Pressure::from_str(val.as_ref()).unwrap();
+			}
+			if Distance::VARIANTS.as_ref().contains(&val.as_ref()) {
+				self.distance = Distance::from_str(val.as_ref()).unwrap();
+			}
 		}
 
 		self
@@ -79,6 +215,38 @@ impl Units {
 mod tests {
 	use super::*;
 
+	#[test]
+	fn units_from_language() {
+		assert_eq!(
+			Units::for_language("en_US"),
+			Units {
+				temperature: Temperature::fahrenheit,
+				speed: Speed::mph,
+				time: Time::am_pm,
+				precipitation: Precipitation::inch,
+				pressure: Pressure::inhg,
+				distance: Distance::mi,
+			}
+		);
+		assert_eq!(Units::for_language("en_GB").speed, Speed::mph);
+		assert_eq!(Units::for_language("en_GB").temperature, Temperature::celsius);
+		assert_eq!(Units::for_language("de_DE"), Units::default());
+		assert_eq!(Units::for_language("de"), Units::default());
+	}
+
+	#[test]
+	fn conversions() {
+		assert_eq!(Temperature::kelvin.api_name(), "celsius");
+		assert!((Temperature::kelvin.convert_api_value(14.0) - 287.15).abs() < 0.01);
+		assert_eq!(Speed::knots.api_name(), "kn");
+		assert!((Speed::beaufort.convert_api_value(0.5)).abs() < f32::EPSILON);
+		assert!((Speed::beaufort.convert_api_value(10.7) - 2.0).abs() < f32::EPSILON);
+		assert!((Speed::beaufort.convert_api_value(130.0) - 12.0).abs() < f32::EPSILON);
+		assert!((Pressure::inhg.convert_api_value(1001.3) - 29.57).abs() < 0.001);
+		assert!((Pressure::mmhg.convert_api_value(1001.3) - 751.0).abs() < 0.001);
+		assert!((Distance::mi.convert_api_value(1609.344) - 1.0).abs() < 0.001);
+	}
+
 	#[test]
 	fn units_from_args() {
 		let arg_units = [UnitArg::Fahrenheit, UnitArg::Mph, UnitArg::AmPm, UnitArg::Inch];
@@ -91,6 +259,7 @@ mod tests {
 				speed: Speed::mph,
 				time: Time::am_pm,
 				precipitation: Precipitation::inch,
+				..Units::default()
 			}
 		);
 	}
@@ -103,6 +272,7 @@ mod tests {
 			speed: Speed::knots,
 			time: Time::am_pm,
 			precipitation: Precipitation::inch,
+			..Units::default()
 		};
 
 		assert_eq!(Units::merge(&arg_units, cfg_units), cfg_units);
@@ -116,6 +286,7 @@ mod tests {
 			speed: Speed::ms,
 			time: Time::military,
 			precipitation: Precipitation::inch,
+			..Units::default()
 		};
 
 		assert_eq!(
@@ -125,7 +296,29 @@ mod tests {
 				speed: cfg_units.speed,
 				time: Time::am_pm,
 				precipitation: cfg_units.precipitation,
+				..Units::default()
+			}
+		);
+	}
+
+	#[test]
+	fn additional_units_from_args() {
+		let arg_units = [UnitArg::Kelvin, UnitArg::Knots, UnitArg::Mmhg, UnitArg::Mi];
+		let cfg_units = Units {
+			pressure: Pressure::kpa,
+			..Units::default()
+		};
+
+		assert_eq!(
+			Units::merge(&arg_units, cfg_units),
+			Units {
+				temperature: Temperature::kelvin,
+				speed: Speed::knots,
+				pressure: Pressure::mmhg,
+				distance: Distance::mi,
+				..Units::default()
 			}
 		);
+		assert_eq!(Units::merge(&[UnitArg::Beaufort], cfg_units).pressure, Pressure::kpa);
 	}
 }
@@ -1,10 +1,20 @@
 use anyhow::{Context, Result};
-use chrono::{Local, NaiveDate};
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
 use optional_struct::{optional_struct, Applicable};
 use serde::Deserialize;
-use std::collections::{HashMap, HashSet};
+use std::{
+	collections::{HashMap, HashSet},
+	ops::Range,
+};
 
-use super::units::{Precipitation, Units};
+use super::{
+	localization::Locales,
+	sun::Daylight,
+	units::{Precipitation, Temperature, Units},
+};
+
+const KELVIN: &str = "K";
 
 // Open meteo json
 // E.g., London:
@@ -21,6 +31,18 @@ pub struct Weather {
 	pub daily_units: DailyUnits,
 	#[optional_rename(OptionalDaily)]
 	pub daily: Daily,
+	#[serde(default)]
+	pub latitude: f64,
+	#[serde(default)]
+	pub longitude: f64,
+	// The location's time zone, the timestamps are in its local time.
+	#[serde(default)]
+	pub timezone: String,
+	#[serde(default)]
+	pub utc_offset_seconds: i32,
+	// Days before today at the start of the daily values, the hourly values start 24 times as many hours earlier.
+	#[serde(skip)]
+	pub past_days: usize,
 }
 
 #[optional_struct]
@@ -48,6 +70,7 @@ pub struct HourlyUnits {
 #[optional_struct]
 #[derive(Deserialize, Debug)]
 pub struct Hourly {
+	pub time: Vec<String>,
 	pub temperature_2m: Vec<f32>,
 	pub relativehumidity_2m: Vec<f32>,
 	pub apparent_temperature: Vec<f32>,
@@ -56,6 +79,9 @@ pub struct Hourly {
 	pub precipitation: Vec<f32>,
 	pub precipitation_probability: Vec<u8>,
 	pub weathercode: Vec<u8>,
+	pub visibility: Vec<f32>,
+	pub windspeed_10m: Vec<f32>,
+	pub uv_index: Vec<f32>,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -80,7 +106,13 @@ pub struct Daily {
 }
 
 impl Weather {
-	pub async fn get(lat: f64, lon: f64, units: &Units) -> Result<Self> {
+	pub async fn get(
+		lat: f64,
+		lon: f64,
+		(past_days, forecast_days): (u8, u8),
+		units: &Units,
+		texts: &Locales,
+	) -> Result<Self> {
 		// TODO: conditionally expand api call
 		let url = format!(
 			"https://api.open-meteo.com/v1/forecast?
@@ -90,24 +122,85 @@ latitude={lat}
 &temperature_unit={}
 &windspeed_unit={}
 &precipitation_unit={}
-&hourly=temperature_2m,relativehumidity_2m,apparent_temperature,surface_pressure,dewpoint_2m,windspeed_10m,weathercode,precipitation,precipitation_probability
+&hourly=temperature_2m,relativehumidity_2m,apparent_temperature,surface_pressure,dewpoint_2m,windspeed_10m,weathercode,precipitation,precipitation_probability,visibility,uv_index
 &daily=weathercode,sunrise,sunset,temperature_2m_max,temperature_2m_min,precipitation_probability_max,apparent_temperature_max,apparent_temperature_min
+&forecast_days={forecast_days}
+&past_days={past_days}
 &timezone=auto",
-			units.temperature.as_ref(),
-			units.speed.as_ref(),
+			units.temperature.api_name(),
+			units.speed.api_name(),
 			if units.precipitation == Precipitation::probability { "mm" } else {units.precipitation.as_ref()},
 		);
 
-		let res = reqwest::get(url)
+		let mut res = reqwest::get(url)
 			.await?
 			.json::<Self>()
 			.await
-			.with_context(|| "Weather data request failed.")?;
+			.with_context(|| texts.errors.weather_request_failed.clone())?;
+		res.convert(*units);
+		res.past_days = past_days.into();
 
 		Ok(res)
 	}
 
-	pub async fn get_date(date: NaiveDate, lat: f64, lon: f64, units: &Units) -> Result<OptionalWeather> {
+	// Index of the daily values of a day relative to today.
+	pub fn day_index(&self, offset: i64) -> Option<usize> {
+		i64::try_from(self.past_days)
+			.ok()
+			.and_then(|today| usize::try_from(today + offset).ok())
+			.filter(|index| *index < self.daily.time.len())
+	}
+
+	// Index of the hourly values of the current hour.
+	pub fn current_hour_index(&self) -> usize {
+		self.hourly
+			.time
+			.partition_point(|time| *time <= self.current_weather.time)
+			.saturating_sub(1)
+	}
+
+	// Indices of the hourly values of a day. Days with a daylight saving time transition have 23 or 25 hours.
+	pub fn hours_of_day(&self, day_index: usize) -> Range<usize> {
+		hours_of_day(&self.hourly.time, &self.daily.time[day_index])
+	}
+
+	// A timestamp of the response as a point in time. Without a known time zone name, the offset of the
+	// response is used.
+	pub fn local_datetime(&self, time: &str) -> Option<DateTime<FixedOffset>> {
+		local_datetime(&self.timezone, self.utc_offset_seconds, time)
+	}
+
+	// A point in time as a timestamp of the response, rounded to the minute.
+	pub fn local_time(&self, time: DateTime<Utc>) -> String {
+		let time = time + Duration::seconds(30);
+		match self.timezone.parse::<Tz>() {
+			Ok(tz) => time.with_timezone(&tz).naive_local(),
+			Err(_) => time.naive_utc() + Duration::seconds(self.utc_offset_seconds.into()),
+		}
+		.format("%Y-%m-%dT%H:%M")
+		.to_string()
+	}
+
+	pub fn daylight(&self, time: &str) -> Daylight {
+		daylight(self.latitude, self.longitude, self.local_datetime(time))
+	}
+
+	// Sunrise and sunset of a day, if the sun rises and sets at all.
+	pub fn sun_times(&self, day_index: usize) -> Option<(&str, &str)> {
+		sun_times(
+			&self.daily.time[day_index],
+			&self.daily.sunrise[day_index],
+			&self.daily.sunset[day_index],
+		)
+	}
+
+	pub async fn get_date(
+		date: NaiveDate,
+		lat: f64,
+		lon: f64,
+		units: &Units,
+		texts: &Locales,
+	) -> Result<OptionalWeather> {
 		// It takes up to five days until temperature data is available in open-meteo's archive.
 		// Therefore, we use the past_days endpoints for the last five days.
 		let base_url = if date.signed_duration_since(Local::now().date_naive()).num_days() > -5 {
@@ -123,19 +216,20 @@ latitude={lat}
 &temperature_unit={}
 &windspeed_unit={}
 &precipitation_unit={}
-&hourly=temperature_2m,precipitation,weathercode
+&hourly=temperature_2m,relativehumidity_2m,surface_pressure,windspeed_10m,precipitation,weathercode
 &daily=weathercode,temperature_2m_max,temperature_2m_min,apparent_temperature_max,apparent_temperature_min,sunrise,sunset,precipitation_sum
 &timezone=auto",
-			units.temperature.as_ref(),
-			units.speed.as_ref(),
+			units.temperature.api_name(),
+			units.speed.api_name(),
 			if units.precipitation == Precipitation::probability { "mm" } else { units.precipitation.as_ref() },
 		);
 
-		let res = reqwest::get(url)
+		let mut res = reqwest::get(url)
 			.await?
 			.json::<OptionalWeather>()
 			.await
-			.with_context(|| "Historical weather data request failed.")?;
+			.with_context(|| texts.errors.historical_weather_request_failed.clone())?;
+		convert_historical(&mut res, *units);
 
 		Ok(res)
 	}
@@ -145,12 +239,134 @@ latitude={lat}
 		lat: f64,
 		lon: f64,
 		units: &Units,
+		texts: &Locales,
 	) -> Result<HashMap<&'a NaiveDate, OptionalWeather>> {
 		let mut res = HashMap::new();
 		for date in dates {
-			res.insert(date, Self::get_date(*date, lat, lon, units).await?);
+			res.insert(date, Self::get_date(*date, lat, lon, units, texts).await?);
 		}
 
 		Ok(res)
 	}
+
+	// Converts the values that are requested in other units, see `Temperature::convert_api_value`.
+	fn convert(&mut self, units: Units) {
+		let (current, hourly, daily) = (&mut self.current_weather, &mut self.hourly, &mut self.daily);
+		current.temperature = units.temperature.convert_api_value(current.temperature);
+		current.windspeed = units.speed.convert_api_value(current.windspeed);
+		for values in [
+			&mut hourly.temperature_2m,
+			&mut hourly.apparent_temperature,
+			&mut hourly.dewpoint_2m,
+			&mut daily.temperature_2m_max,
+			&mut daily.temperature_2m_min,
+			&mut daily.apparent_temperature_max,
+			&mut daily.apparent_temperature_min,
+		] {
+			convert(values, |v| units.temperature.convert_api_value(v));
+		}
+		convert(&mut hourly.surface_pressure, |v| units.pressure.convert_api_value(v));
+		convert(&mut hourly.windspeed_10m, |v| units.speed.convert_api_value(v));
+		convert(&mut hourly.visibility, |v| units.distance.convert_api_value(v));
+
+		if units.temperature == Temperature::kelvin {
+			let hourly_units = &mut self.hourly_units;
+			for unit in [
+				&mut hourly_units.temperature_2m,
+				&mut hourly_units.apparent_temperature,
+				&mut hourly_units.dewpoint_2m,
+				&mut self.daily_units.temperature_2m_max,
+				&mut self.daily_units.temperature_2m_min,
+			] {
+				*unit = KELVIN.to_string();
+			}
+		}
+	}
+}
+
+impl OptionalWeather {
+	pub fn daylight(&self, time: &str) -> Daylight {
+		let dt = local_datetime(
+			self.timezone.as_deref().unwrap_or_default(),
+			self.utc_offset_seconds.unwrap_or_default(),
+			time,
+		);
+		daylight(self.latitude.unwrap_or_default(), self.longitude.unwrap_or_default(), dt)
+	}
+
+	// Sunrise and sunset of the requested day, if the sun rises and sets at all.
+	pub fn sun_times(&self) -> Option<(&str, &str)> {
+		let daily = &self.daily;
+		sun_times(
+			daily.time.as_ref()?.first()?,
+			daily.sunrise.as_ref()?.first()?,
+			daily.sunset.as_ref()?.first()?,
+		)
+	}
+}
+
+// A timestamp of a response as a point in time.
+fn local_datetime(timezone: &str, utc_offset_seconds: i32, time: &str) -> Option<DateTime<FixedOffset>> {
+	let naive = NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").ok()?;
+	match timezone.parse::<Tz>() {
+		// The earlier of repeated times when the clocks are turned back
+		Ok(tz) => tz.from_local_datetime(&naive).earliest().map(|dt| dt.fixed_offset()),
+		Err(_) => FixedOffset::east_opt(utc_offset_seconds)?.from_local_datetime(&naive).single(),
+	}
+}
+
+// Times that don't exist, e.g., skipped when the clocks are turned forward, count as day.
+fn daylight(lat: f64, lon: f64, dt: Option<DateTime<FixedOffset>>) -> Daylight {
+	dt.map_or(Daylight::Day, |dt| Daylight::at(lat, lon, dt.with_timezone(&Utc)))
+}
+
+// During polar day and polar night, the times of a day's sunrise and sunset aren't on that day.
+fn sun_times<'a>(date: &str, sunrise: &'a str, sunset: &'a str) -> Option<(&'a str, &'a str)> {
+	(sunrise.starts_with(date) && sunset.starts_with(date) && sunrise < sunset).then_some((sunrise, sunset))
+}
+
+pub fn hours_of_day(times: &[String], date: &str) -> Range<usize> {
+	times.partition_point(|time| time.as_str() < date)..times.partition_point(|time| time.get(..10) <= Some(date))
+}
+
+// Hour of the day of a timestamp of the response.
+pub fn hour_of(time: &str) -> usize {
+	NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").map_or(0, |dt| dt.hour() as usize)
+}
+
+fn convert_historical(weather: &mut OptionalWeather, units: Units) {
+	let (hourly, daily) = (&mut weather.hourly, &mut weather.daily);
+	for values in [
+		&mut hourly.temperature_2m,
+		&mut daily.temperature_2m_max,
+		&mut daily.temperature_2m_min,
+		&mut daily.apparent_temperature_max,
+		&mut daily.apparent_temperature_min,
+	]
+	.into_iter()
+	.flatten()
+	{
+		convert(values, |v| units.temperature.convert_api_value(v));
+	}
+	if let Some(values) = hourly.surface_pressure.as_mut() {
+		convert(values, |v| units.pressure.convert_api_value(v));
+	}
+	if let Some(values) = hourly.windspeed_10m.as_mut() {
+		convert(values, |v| units.speed.convert_api_value(v));
+	}
+
+	if let Some(daily_units) = weather
+		.daily_units
+		.as_mut()
+		.filter(|_| units.temperature == Temperature::kelvin)
+	{
+		daily_units.temperature_2m_max = KELVIN.to_string();
+		daily_units.temperature_2m_min = KELVIN.to_string();
+	}
+}
+
+fn convert(values: &mut [f32], f: impl Fn(f32) -> f32) {
+	for value in values {
+		*value = f(*value);
+	}
 }